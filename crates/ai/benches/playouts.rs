@@ -0,0 +1,53 @@
+//! Compares random playouts on a `Game` against random and heavy playouts on a `Bitboard`.
+//!
+//! Run with `cargo bench -p ai --bench playouts`.
+
+use std::time::{Duration, Instant};
+
+use ai::{
+    playout::{bitboard_playout, game_playout, heavy_playout},
+    playout_policy::PlayoutWeights,
+};
+use fastrand::Rng;
+use game::{
+    bitboard::Bitboard,
+    game::{Game, Turn},
+};
+
+/// The number of playouts timed for each representation
+const PLAYOUTS: usize = 2000;
+
+/// Times `PLAYOUTS` runs of `f`
+fn time(mut f: impl FnMut() -> f32) -> Duration {
+    let start = Instant::now();
+    let mut total = 0.0;
+    for _ in 0..PLAYOUTS {
+        total += f();
+    }
+    // Keep the results alive so that the playouts aren't optimised away
+    std::hint::black_box(total);
+    start.elapsed()
+}
+
+fn main() {
+    for depth in 1..=3 {
+        let g = Game::new_depth(depth, 0);
+        let b = Bitboard::from_game(&g).unwrap();
+
+        let mut rng = Rng::new();
+        let slow = time(|| game_playout(g.clone(), Turn::Player1, None, &mut rng));
+        let fast = time(|| bitboard_playout(b.clone(), Turn::Player1, None, &mut rng));
+        let heavy = time(|| heavy_playout(b.clone(), Turn::Player1, PlayoutWeights::default(), None, &mut rng));
+
+        println!(
+            "Depth {depth}: Game {:.2}us, Bitboard {:.2}us per playout ({:.1}x faster)",
+            slow.as_secs_f64() * 1e6 / PLAYOUTS as f64,
+            fast.as_secs_f64() * 1e6 / PLAYOUTS as f64,
+            slow.as_secs_f64() / fast.as_secs_f64()
+        );
+        println!(
+            "Depth {depth}: Heavy bitboard {:.2}us per playout",
+            heavy.as_secs_f64() * 1e6 / PLAYOUTS as f64
+        );
+    }
+}
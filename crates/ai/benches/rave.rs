@@ -0,0 +1,75 @@
+//! Plays Monte Carlo searches with RAVE against plain UCB1 selection, with the same number of simulations each.
+//!
+//! Run with `cargo bench -p ai --bench rave`.
+
+use std::time::{Duration, Instant};
+
+use ai::{monte_carlo::MonteCarloManager, monte_carlo_policy::MonteCarloPolicy};
+use game::{
+    game::{Game, Turn},
+    value::Value,
+};
+
+/// The depth of the games played
+const DEPTH: usize = 2;
+/// The number of games played, alternating who goes first
+const GAMES: usize = 10;
+/// The number of simulations for each move
+const SIMS: usize = 2000;
+/// The exploration factor used by both sides
+const EXPLORATION_FACTOR: f32 = 0.7;
+/// The RAVE equivalence parameter
+const RAVE: f32 = 300.0;
+
+/// Searches the position and returns the move chosen, adding the time taken to `elapsed`
+fn search(g: &Game, rave: Option<f32>, elapsed: &mut Duration) -> Vec<usize> {
+    let start = Instant::now();
+    let mut m = MonteCarloManager::new(g.clone(), g.turn);
+    m.rave = rave;
+    m.search(SIMS, EXPLORATION_FACTOR, g.turn);
+    let mv = m
+        .best(MonteCarloPolicy::Robust, g.turn, EXPLORATION_FACTOR)
+        .unwrap();
+    *elapsed += start.elapsed();
+    mv
+}
+
+fn main() {
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+    let mut rave_time = Duration::ZERO;
+    let mut plain_time = Duration::ZERO;
+    let mut rave_moves = 0;
+    let mut plain_moves = 0;
+
+    for n in 0..GAMES {
+        let rave_turn = if n % 2 == 0 {
+            Turn::Player1
+        } else {
+            Turn::Player2
+        };
+        let mut g = Game::new_depth(DEPTH, 0);
+        while g.board.check() == Value::None {
+            let mv = if g.turn == rave_turn {
+                rave_moves += 1;
+                search(&g, Some(RAVE), &mut rave_time)
+            } else {
+                plain_moves += 1;
+                search(&g, None, &mut plain_time)
+            };
+            g.play(&mv).unwrap();
+        }
+
+        match g.board.check() {
+            Value::Draw => draws += 1,
+            x if x == rave_turn.val() => wins += 1,
+            _ => losses += 1,
+        }
+        println!("Game {}: RAVE {wins}W {draws}D {losses}L", n + 1);
+    }
+
+    println!(
+        "RAVE {:.1}ms per move, plain UCB1 {:.1}ms per move",
+        rave_time.as_secs_f64() * 1000.0 / rave_moves as f64,
+        plain_time.as_secs_f64() * 1000.0 / plain_moves as f64
+    );
+}
@@ -0,0 +1,33 @@
+use game::game::Turn;
+
+/// The moves each player made during a simulation, for all-moves-as-first (RAVE) statistics
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmafMoves {
+    /// A bitset of the cells played in by each player, indexed by `cell_index`
+    cells: [Vec<u64>; 2],
+}
+
+/// Returns a unique index for each cell of a game, counting across the bottom level boards
+pub fn cell_index(pos: &[usize]) -> usize {
+    pos.iter().fold(0, |acc, x| acc * 9 + x)
+}
+
+impl AmafMoves {
+    /// Creates an empty set for a game of the given depth
+    pub fn new(depth: usize) -> AmafMoves {
+        let words = 9usize.pow(depth as u32).div_ceil(64);
+        AmafMoves {
+            cells: [vec![0; words], vec![0; words]],
+        }
+    }
+
+    /// Records that `turn` played in the cell with the given index
+    pub fn insert(&mut self, index: usize, turn: Turn) {
+        self.cells[turn as usize][index / 64] |= 1 << (index % 64);
+    }
+
+    /// Returns whether `turn` played in the cell with the given index
+    pub fn contains(&self, index: usize, turn: Turn) -> bool {
+        self.cells[turn as usize][index / 64] & (1 << (index % 64)) != 0
+    }
+}
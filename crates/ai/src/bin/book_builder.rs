@@ -0,0 +1,162 @@
+//! Builds an opening book for Noughbert from long searches of the positions reached in self-play.
+//!
+//! Run with `cargo run --release -p ai --bin book_builder -- [OPTIONS]`, where the options are:
+//!
+//! - `--games N`: the number of self-play games (default 20)
+//! - `--plies N`: the number of moves of each game to search and add to the book (default 4)
+//! - `--depth N`: the depth of the board (default 2)
+//! - `--time N`: the time each position is searched for, in seconds (default 30)
+//! - `--threads N`: the number of threads for each search (default 1)
+//! - `--seed N`: the seed for the choice of book moves that the games follow (default 0)
+//! - `--output PATH`: the book to extend, or create (default `opening_book.json` beside the
+//!   executable, or the path in the `NOUGHBERT_BOOK` environment variable)
+//!
+//! Positions already in the book aren't searched again. Each game follows the book with some
+//! randomness, so that later games reach new positions. The book is saved after every search, so
+//! the builder can be stopped at any time.
+//!
+//! The game reads the book from the same place, so a book built with `--release` is used by
+//! release builds of the game. For example, to build a book of the first two moves at depth 2:
+//!
+//! `cargo run --release -p ai --bin book_builder -- --games 20 --plies 2`
+
+use std::{env, f32::consts::FRAC_1_SQRT_2, path::PathBuf, sync::mpsc, thread, time::Duration};
+
+use ai::{
+    book_mode::BookMode,
+    comms::Comms,
+    monte_carlo_parallelism::MonteCarloParallelism,
+    monte_carlo_policy::MonteCarloPolicy,
+    monte_carlo_settings::MonteCarloSettings,
+    noughbert::noughbert,
+    noughbert_message::NoughbertMessage,
+    opening_book::{opening_book_path, BookMove, OpeningBook},
+    playout_policy::PlayoutPolicy,
+};
+use anyhow::{bail, Context, Result};
+use fastrand::Rng;
+use game::{game::Game, value::Value};
+
+/// The settings of the build
+struct Options {
+    games: usize,
+    plies: usize,
+    depth: usize,
+    time: f32,
+    threads: usize,
+    seed: u64,
+    output: PathBuf,
+}
+
+/// Reads the options from the command line
+fn parse_options() -> Result<Options> {
+    let mut options = Options {
+        games: 20,
+        plies: 4,
+        depth: 2,
+        time: 30.0,
+        threads: 1,
+        seed: 0,
+        output: opening_book_path(),
+    };
+    let mut args = env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .with_context(|| format!("Missing a value for `{flag}`"))?;
+        match flag.as_str() {
+            "--games" => options.games = value.parse()?,
+            "--plies" => options.plies = value.parse()?,
+            "--depth" => options.depth = value.parse()?,
+            "--time" => options.time = value.parse()?,
+            "--threads" => options.threads = value.parse::<usize>()?.max(1),
+            "--seed" => options.seed = value.parse()?,
+            "--output" => options.output = PathBuf::from(value),
+            _ => bail!("Unknown option `{flag}`"),
+        }
+    }
+    Ok(options)
+}
+
+/// Searches a position until the time is up, returning the statistics of every move searched
+fn search(main: &Comms<NoughbertMessage>, g: &Game, options: &Options) -> Vec<BookMove> {
+    let settings = MonteCarloSettings {
+        game: g.clone(),
+        timeout: Duration::from_secs_f32(options.time),
+        max_sims: usize::MAX,
+        threads: options.threads,
+        exploration_factor: FRAC_1_SQRT_2,
+        opt_for: g.turn,
+        carry_forward: false,
+        policy: MonteCarloPolicy::Robust,
+        parallelism: MonteCarloParallelism::Tree,
+        transpositions: false,
+        playout: PlayoutPolicy::Random,
+        rave: None,
+        // Search endlessly, so that the statistics can be asked for before stopping it
+        endless: true,
+        seed: None,
+        book: BookMode::Off,
+        perfect: false,
+    };
+    main.send(NoughbertMessage::Start(settings)).unwrap();
+    thread::sleep(Duration::from_secs_f32(options.time));
+
+    main.send(NoughbertMessage::GetThoughts(g.turn)).unwrap();
+    let thoughts = loop {
+        if let NoughbertMessage::Thoughts(t) = main.recv().unwrap() {
+            break t;
+        }
+    };
+    main.send(NoughbertMessage::Interrupt).unwrap();
+
+    thoughts
+        .moves
+        .into_iter()
+        .filter(|x| x.sims > 0)
+        .map(|x| BookMove {
+            play: x.play,
+            sims: x.sims,
+            mean: x.mean,
+        })
+        .collect()
+}
+
+fn main() -> Result<()> {
+    let options = parse_options()?;
+    let mut book = OpeningBook::load(&options.output).unwrap_or_default();
+    println!("Starting from {} positions", book.len());
+
+    let (tx_0, rx_0) = mpsc::sync_channel(0);
+    let (tx_1, rx_1) = mpsc::sync_channel(1);
+    thread::spawn(move || noughbert(Comms::new(rx_0, tx_1)));
+    let main = Comms::new(rx_1, tx_0);
+
+    let mut rng = Rng::with_seed(options.seed);
+    for n in 0..options.games {
+        let mut g = Game::new_depth(options.depth, 0);
+        for _ in 0..options.plies {
+            if g.board.check() != Value::None {
+                break;
+            }
+            if book.get(&g).is_none() {
+                let moves = search(&main, &g, &options);
+                book.insert(&g, moves);
+                book.save(&options.output)?;
+                println!("Searched {} positions", book.len());
+            }
+            let Some(play) = book.choose(&g, BookMode::Varied, &mut rng) else {
+                break;
+            };
+            g.play(&play)?;
+        }
+        println!("Game {}/{} done", n + 1, options.games);
+    }
+
+    println!(
+        "Saved {} positions to {}",
+        book.len(),
+        options.output.display()
+    );
+    Ok(())
+}
@@ -0,0 +1,303 @@
+//! Plays a match between two computer players, reporting the results and the Elo difference between them.
+//!
+//! Run with `cargo run --release -p ai --bin match_runner -- [OPTIONS]`, where the options are:
+//!
+//! - `--games N`: the number of games to play (default 20)
+//! - `--depth N`: the depth of the board (default 2)
+//! - `--openings N`: the number of fixed openings, each played once with either player going first (default 10)
+//! - `--plies N`: the number of random moves in each opening (default 2)
+//! - `--seed N`: the seed the openings are made from (default 0)
+//! - `--a SPEC` and `--b SPEC`: the two players
+//!
+//! A `SPEC` is a comma-separated list of `key=value` settings, any of which may be left out: `engine`
+//! (Noughbert, Thorn or Bramble), `sims`, `time` (in seconds), `policy`, `exploration`, `threads`,
+//! `parallelism` (Tree or Root), `playout` (Random or Heavy), `rave`, `transpositions`, `seed` and
+//! `book` (Off, Best or Varied, and Off unless set). For example, to compare the ways of spreading a
+//! search over four threads:
+//!
+//! `--a threads=4,parallelism=tree --b threads=4,parallelism=root`
+
+use std::{
+    env,
+    f32::consts::FRAC_1_SQRT_2,
+    time::{Duration, Instant},
+};
+
+use ai::{
+    book_mode::BookMode, engine::Engine, engine_player::EnginePlayer,
+    monte_carlo_parallelism::MonteCarloParallelism, monte_carlo_policy::MonteCarloPolicy,
+    monte_carlo_settings::MonteCarloSettings, playout_policy::PlayoutPolicy,
+};
+use anyhow::{bail, Context, Result};
+use fastrand::Rng;
+use game::{
+    game::{Game, Turn},
+    value::Value,
+};
+
+/// The number of simulations per move for a player that doesn't set them
+const DEFAULT_SIMS: usize = 2000;
+/// The time limit per move for a player that doesn't set one (seconds)
+const DEFAULT_TIME: u64 = 10;
+/// The number of standard errors either side of the score covered by the 95% interval
+const Z_95: f64 = 1.96;
+
+/// The settings of the match as a whole
+struct Options {
+    games: usize,
+    depth: usize,
+    openings: usize,
+    plies: usize,
+    seed: u64,
+    a: String,
+    b: String,
+}
+
+/// The time a player spent on its moves
+#[derive(Default)]
+struct MoveTimes {
+    moves: u32,
+    total: Duration,
+}
+
+/// Reads the options from the command line
+fn parse_options() -> Result<Options> {
+    let mut options = Options {
+        games: 20,
+        depth: 2,
+        openings: 10,
+        plies: 2,
+        seed: 0,
+        a: String::new(),
+        b: String::new(),
+    };
+    let mut args = env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .with_context(|| format!("Missing a value for `{flag}`"))?;
+        match flag.as_str() {
+            "--games" => options.games = value.parse()?,
+            "--depth" => options.depth = value.parse()?,
+            "--openings" => options.openings = value.parse()?,
+            "--plies" => options.plies = value.parse()?,
+            "--seed" => options.seed = value.parse()?,
+            "--a" => options.a = value,
+            "--b" => options.b = value,
+            _ => bail!("Unknown option `{flag}`"),
+        }
+    }
+    if options.games == 0 || options.openings == 0 {
+        bail!("At least one game and one opening are needed");
+    }
+    Ok(options)
+}
+
+/// Returns the variant called `value`, found by cycling through them all from `first`
+fn parse_named<T: Copy>(
+    value: &str,
+    first: T,
+    name: fn(&T) -> String,
+    next: fn(T) -> T,
+) -> Result<T> {
+    let mut x = first;
+    loop {
+        if name(&x).eq_ignore_ascii_case(value) {
+            return Ok(x);
+        }
+        x = next(x);
+        if name(&x) == name(&first) {
+            bail!("Unknown value `{value}`");
+        }
+    }
+}
+
+/// Builds a player from a comma-separated list of `key=value` settings
+fn parse_player(spec: &str, depth: usize) -> Result<EnginePlayer> {
+    let mut engine = Engine::Noughbert;
+    let mut settings = MonteCarloSettings {
+        game: Game::new_depth(depth, 0),
+        timeout: Duration::from_secs(DEFAULT_TIME),
+        max_sims: DEFAULT_SIMS,
+        threads: 1,
+        exploration_factor: FRAC_1_SQRT_2,
+        opt_for: Turn::Player1,
+        carry_forward: true,
+        policy: MonteCarloPolicy::Robust,
+        parallelism: MonteCarloParallelism::Tree,
+        transpositions: false,
+        playout: PlayoutPolicy::Random,
+        rave: None,
+        endless: false,
+        seed: None,
+        book: BookMode::Off,
+        perfect: false,
+    };
+    for setting in spec.split(',').filter(|x| !x.is_empty()) {
+        let Some((key, value)) = setting.split_once('=') else {
+            bail!("Expected `key=value`, found `{setting}`");
+        };
+        match key {
+            "engine" => engine = parse_named(value, Engine::Noughbert, Engine::name, Engine::next)?,
+            "sims" => settings.max_sims = value.parse()?,
+            "time" => settings.timeout = Duration::from_secs_f32(value.parse()?),
+            "policy" => {
+                settings.policy = parse_named(
+                    value,
+                    MonteCarloPolicy::Robust,
+                    MonteCarloPolicy::name,
+                    MonteCarloPolicy::next,
+                )?
+            }
+            "exploration" => settings.exploration_factor = value.parse()?,
+            "threads" => settings.threads = value.parse::<usize>()?.max(1),
+            "parallelism" => {
+                settings.parallelism = parse_named(
+                    value,
+                    MonteCarloParallelism::Tree,
+                    MonteCarloParallelism::name,
+                    MonteCarloParallelism::next,
+                )?
+            }
+            "playout" => {
+                settings.playout = parse_named(
+                    value,
+                    PlayoutPolicy::Random,
+                    PlayoutPolicy::name,
+                    PlayoutPolicy::next,
+                )?
+            }
+            "rave" => settings.rave = Some(value.parse()?),
+            "transpositions" => settings.transpositions = value.parse()?,
+            "seed" => settings.seed = Some(value.parse()?),
+            "book" => {
+                settings.book = parse_named(value, BookMode::Off, BookMode::name, BookMode::next)?
+            }
+            _ => bail!("Unknown setting `{key}`"),
+        }
+    }
+    Ok(EnginePlayer::new(engine, settings))
+}
+
+/// Describes a player's settings in a line
+fn describe(player: &EnginePlayer) -> String {
+    let s = &player.settings;
+    format!(
+        "{}, {} sims, {}s, {} policy, exploration {}, {} thread(s), {} parallelism, {} playouts, {} book",
+        player.engine.name(),
+        s.max_sims,
+        s.timeout.as_secs_f32(),
+        s.policy.name(),
+        s.exploration_factor,
+        s.threads,
+        s.parallelism.name(),
+        s.playout.name(),
+        s.book.name(),
+    )
+}
+
+/// Makes each opening by playing `plies` random moves from the start
+fn openings(depth: usize, count: usize, plies: usize, seed: u64) -> Vec<Game> {
+    let mut rng = Rng::with_seed(seed);
+    (0..count)
+        .map(|_| {
+            let mut g = Game::new_depth(depth, 0);
+            for _ in 0..plies {
+                if g.board.check() != Value::None {
+                    break;
+                }
+                let mv = rng.choice(g.legal_moves()).unwrap();
+                g.play(&mv).unwrap();
+            }
+            g
+        })
+        .collect()
+}
+
+/// Returns the Elo difference at which the stronger player is expected to score `score`, from 0 to 1.
+/// The score is kept half a game away from 0 and 1, where the difference would be infinite
+fn elo(score: f64, games: f64) -> f64 {
+    let score = score.clamp(0.5 / games, 1.0 - 0.5 / games);
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+fn main() -> Result<()> {
+    let options = parse_options()?;
+    let a = parse_player(&options.a, options.depth).context("Invalid settings for A")?;
+    let b = parse_player(&options.b, options.depth).context("Invalid settings for B")?;
+    println!("A: {}", describe(&a));
+    println!("B: {}", describe(&b));
+
+    let openings = openings(options.depth, options.openings, options.plies, options.seed);
+    let (mut wins, mut draws, mut losses) = (0, 0, 0);
+    let mut a_times = MoveTimes::default();
+    let mut b_times = MoveTimes::default();
+
+    for n in 0..options.games {
+        // Each opening is played twice in a row, with the players swapping sides
+        let mut g = openings[(n / 2) % openings.len()].clone();
+        let a_turn = if n % 2 == 0 {
+            Turn::Player1
+        } else {
+            Turn::Player2
+        };
+
+        while g.board.check() == Value::None {
+            let (player, times) = if g.turn == a_turn {
+                (&a, &mut a_times)
+            } else {
+                (&b, &mut b_times)
+            };
+            let start = Instant::now();
+            let mv = player
+                .play(&g)
+                .with_context(|| format!("{} made no move", player.engine.name()))?;
+            times.total += start.elapsed();
+            times.moves += 1;
+            g.play(&mv)?;
+        }
+
+        match g.board.check() {
+            Value::Draw => draws += 1,
+            x if x == a_turn.val() => wins += 1,
+            _ => losses += 1,
+        }
+        println!(
+            "Game {}/{}: A as {:?}, {:?}. A {wins}W {draws}D {losses}L",
+            n + 1,
+            options.games,
+            a_turn,
+            g.board.check()
+        );
+    }
+
+    // Score each game as 1 for a win, 0.5 for a draw and 0 for a loss, from A's side
+    let games = (wins + draws + losses) as f64;
+    let score = (wins as f64 + 0.5 * draws as f64) / games;
+    let variance = (wins as f64 * (1.0 - score).powi(2)
+        + draws as f64 * (0.5 - score).powi(2)
+        + losses as f64 * score.powi(2))
+        / games;
+    let error = Z_95 * (variance / games).sqrt();
+
+    println!();
+    println!(
+        "A vs B: {wins}W {draws}D {losses}L, scoring {:.1}%",
+        score * 100.0
+    );
+    println!(
+        "Elo difference: {:+.1} (95% interval {:+.1} to {:+.1})",
+        elo(score, games),
+        elo(score - error, games),
+        elo(score + error, games)
+    );
+    for (name, times) in [("A", a_times), ("B", b_times)] {
+        println!(
+            "{name}: {:.1}ms per move over {} moves",
+            times.total.as_secs_f64() * 1000.0 / times.moves.max(1) as f64,
+            times.moves
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+/// The ways in which Noughbert may use the opening book
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookMode {
+    /// The book is ignored
+    Off,
+    /// The most searched book move is always played
+    Best,
+    /// A well searched book move is picked at random, weighted by how much it was searched
+    Varied,
+}
+
+impl BookMode {
+    pub fn name(&self) -> String {
+        match self {
+            BookMode::Off => "Off".to_owned(),
+            BookMode::Best => "Best".to_owned(),
+            BookMode::Varied => "Varied".to_owned(),
+        }
+    }
+
+    pub fn next(self) -> BookMode {
+        match self {
+            BookMode::Off => Self::Best,
+            BookMode::Best => Self::Varied,
+            BookMode::Varied => Self::Off,
+        }
+    }
+}
@@ -0,0 +1,324 @@
+use std::{cmp::Reverse, sync::mpsc, time::Instant};
+
+use game::{
+    game::{Game, Turn},
+    value::Value,
+    Move,
+};
+
+use crate::{
+    bramble_eval::{evaluate, EVAL_SCALE, WIN_SCORE},
+    monte_carlo_proof::MonteCarloProof,
+    monte_carlo_settings::MonteCarloSettings,
+    monte_carlo_stop::MonteCarloStop,
+    move_thoughts::MoveThoughts,
+    noughbert_message::NoughbertMessage,
+    thoughts::Thoughts,
+};
+
+use super::comms::Comms;
+
+/// The number of positions searched for each simulation allowed, so the strength settings scale Bramble too
+pub const BRAMBLE_NODES_PER_SIM: usize = 64;
+/// The number of positions searched between checks of the clock and the main thread
+const POLL_INTERVAL: usize = 1024;
+
+/// Why a search was cut short
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stop {
+    /// The time or position budget ran out
+    Budget,
+    /// The main thread asked for the best move so far
+    Return,
+    /// The main thread no longer wants a move
+    Interrupt,
+}
+
+/// The state of an iterative deepening search
+struct Search<'a> {
+    main: &'a Comms<NoughbertMessage>,
+    options: &'a MonteCarloSettings,
+    start_time: Instant,
+    max_nodes: usize,
+    /// The number of positions searched so far
+    nodes: usize,
+    /// The score of the best move found by the last completed iteration
+    score: i32,
+    /// Each root move of the last completed iteration, with its score and the positions searched
+    /// below it, best first
+    moves: Vec<(Move, i32, usize)>,
+    /// The reason the search was cut short, if it was
+    stop: Option<Stop>,
+}
+
+impl Search<'_> {
+    /// Checks the budget and answers the main thread, returning whether to stop
+    fn poll(&mut self) -> bool {
+        if self.stop.is_some() {
+            return true;
+        }
+        // An endless search only stops when it is told to
+        if !self.options.endless
+            && (self.start_time.elapsed() >= self.options.timeout || self.nodes >= self.max_nodes)
+        {
+            self.stop = Some(Stop::Budget);
+            return true;
+        }
+
+        match self.main.try_recv() {
+            Ok(m) => self.answer(m),
+            Err(e) => match e {
+                mpsc::TryRecvError::Empty => {}
+                mpsc::TryRecvError::Disconnected => panic!("Thread disconnected"),
+            },
+        }
+        self.stop.is_some()
+    }
+
+    /// Answers the main thread until it asks for the move or interrupts, once there is nothing left
+    /// to search
+    fn idle(&mut self) {
+        while self.stop.is_none() {
+            let Ok(m) = self.main.recv() else {
+                panic!("Thread disconnected");
+            };
+            self.answer(m);
+        }
+    }
+
+    /// Handles a message from the main thread
+    fn answer(&mut self, m: NoughbertMessage) {
+        match m {
+            NoughbertMessage::Interrupt => self.stop = Some(Stop::Interrupt),
+            NoughbertMessage::Return() => self.stop = Some(Stop::Return),
+            NoughbertMessage::GetThoughts(t) => {
+                self.main
+                    .send(NoughbertMessage::Thoughts(self.thoughts(t)))
+                    .unwrap();
+            }
+            NoughbertMessage::Start(_) => {}
+            NoughbertMessage::Thoughts(_) => {}
+            NoughbertMessage::Move(..) => {}
+        }
+    }
+
+    /// Converts a score for the player to move at the root into a mean from -1 to 1 and, for a
+    /// forced result, the number of plies until the end
+    fn outcome(score: i32) -> (f32, Option<MonteCarloProof>) {
+        if score.abs() > WIN_SCORE - 1000 {
+            let plies = (WIN_SCORE - score.abs()) as usize;
+            let proof = if score > 0 {
+                MonteCarloProof::Win(plies)
+            } else {
+                MonteCarloProof::Loss(plies)
+            };
+            (score.signum() as f32, Some(proof))
+        } else {
+            ((score as f32 / EVAL_SCALE).clamp(-1.0, 1.0), None)
+        }
+    }
+
+    /// Reports the search so far, with the score spread over the positions searched like a Monte Carlo total.
+    /// Only the best root move is scored exactly, the rest are bounds that were enough to refute them
+    fn thoughts(&self, t: Turn) -> Thoughts {
+        let flip = if t == self.options.game.turn {
+            1.0
+        } else {
+            -1.0
+        };
+        let for_t = |p: MonteCarloProof| if flip > 0.0 { p } else { p.flip() };
+
+        let (eval, proven) = Self::outcome(self.score);
+        let moves = self
+            .moves
+            .iter()
+            .map(|(mv, score, nodes)| {
+                let (mean, proven) = Self::outcome(*score);
+                MoveThoughts {
+                    play: mv.clone(),
+                    sims: *nodes,
+                    mean: flip * mean,
+                    // Move proofs start counting after the move itself
+                    proven: proven.map(|p| {
+                        for_t(match p {
+                            MonteCarloProof::Win(n) => MonteCarloProof::Win(n - 1),
+                            MonteCarloProof::Loss(n) => MonteCarloProof::Loss(n - 1),
+                            MonteCarloProof::Draw => MonteCarloProof::Draw,
+                        })
+                    }),
+                    pv: vec![],
+                }
+            })
+            .collect();
+
+        Thoughts {
+            sims: self.nodes,
+            score: flip * eval * self.nodes as f32,
+            proven: proven.map(for_t),
+            moves,
+            started: self.nodes,
+            duplicate_leaves: 0,
+        }
+    }
+
+    /// Scores the position for the player to move, looking `depth` moves ahead
+    fn negamax(&mut self, g: &mut Game, depth: usize, mut alpha: i32, beta: i32, ply: i32) -> i32 {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(POLL_INTERVAL) && self.poll() {
+            return 0;
+        }
+
+        // The player who just moved may have ended the game
+        match g.board.check() {
+            Value::None => {}
+            Value::Draw => return 0,
+            _ => return -(WIN_SCORE - ply),
+        }
+        if depth == 0 {
+            return evaluate(g);
+        }
+
+        let mut best = -WIN_SCORE;
+        for mv in g.legal_moves() {
+            g.play(&mv).unwrap();
+            let score = -self.negamax(g, depth - 1, -beta, -alpha, ply + 1);
+            g.unplay().unwrap();
+            if self.stop.is_some() {
+                return 0;
+            }
+
+            best = best.max(score);
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        best
+    }
+
+    /// Searches every move at the root, trying `first` before the rest, and returns the best. The
+    /// scores of all the moves are kept once every one has been searched
+    fn search_root(
+        &mut self,
+        g: &mut Game,
+        depth: usize,
+        first: &Option<Move>,
+    ) -> Option<(Move, i32)> {
+        let mut moves = g.legal_moves();
+        if let Some(first) = first {
+            if let Some(i) = moves.iter().position(|x| x == first) {
+                let mv = moves.remove(i);
+                moves.insert(0, mv);
+            }
+        }
+
+        let mut best: Option<(Move, i32)> = None;
+        let mut alpha = -WIN_SCORE - 1;
+        let mut scored = vec![];
+        for mv in moves {
+            let nodes = self.nodes;
+            g.play(&mv).unwrap();
+            let score = -self.negamax(g, depth - 1, -WIN_SCORE - 1, -alpha, 1);
+            g.unplay().unwrap();
+            if self.stop.is_some() {
+                return None;
+            }
+            if score > alpha {
+                alpha = score;
+                best = Some((mv.clone(), score));
+            }
+            scored.push((mv, score, self.nodes - nodes));
+        }
+
+        // Ties keep the search order, so the best move stays first
+        scored.sort_by_key(|x| Reverse(x.1));
+        self.moves = scored;
+        best
+    }
+}
+
+/// Bramble: an alpha-beta search with iterative deepening and a hand-written evaluation
+pub fn bramble(main: Comms<NoughbertMessage>) {
+    loop {
+        // Recieve all messages, if a `NoughbertMessage::Start()` is recieved, begin searching
+        // Stop once the main thread has gone
+        let Ok(message) = main.recv() else {
+            return;
+        };
+        let options = match message {
+            NoughbertMessage::Start(x) => x,
+            NoughbertMessage::Interrupt => continue,
+            NoughbertMessage::GetThoughts(_) => continue,
+            NoughbertMessage::Thoughts(_) => continue,
+            NoughbertMessage::Move(..) => continue,
+            NoughbertMessage::Return() => continue,
+        };
+
+        println!("Bramble: Search requested");
+
+        let mut g = options.game.clone();
+        let mut search = Search {
+            main: &main,
+            options: &options,
+            start_time: Instant::now(),
+            max_nodes: options.max_sims.saturating_mul(BRAMBLE_NODES_PER_SIM),
+            nodes: 0,
+            score: 0,
+            moves: vec![],
+            stop: None,
+        };
+
+        // Search one move deeper each time, keeping the result of the last finished iteration
+        let empty_cells = g.board.moves(&[]).len() - g.moves.len();
+        let mut best: Option<Move> = g.legal_moves().first().cloned();
+        let mut depth = 0;
+        while depth < empty_cells {
+            depth += 1;
+            match search.search_root(&mut g, depth, &best) {
+                Some((mv, score)) => {
+                    best = Some(mv);
+                    search.score = score;
+                    // Once a result is forced, looking deeper won't change it
+                    if score.abs() > WIN_SCORE - 1000 {
+                        break;
+                    }
+                }
+                None => {
+                    depth -= 1;
+                    break;
+                }
+            }
+        }
+
+        // An endless search that has nothing left to search keeps answering until it is stopped
+        if options.endless {
+            search.idle();
+        }
+
+        if search.stop == Some(Stop::Interrupt) {
+            println!("Bramble: Exited due to interrupt request");
+            continue;
+        } else if search.stop == Some(Stop::Return) {
+            println!("Bramble: Exited due to return request");
+        } else if search.stop == Some(Stop::Budget) {
+            println!("Bramble: Exited due to time or position limit");
+        } else {
+            println!("Bramble: Exited due to a forced result or complete search");
+        }
+        println!(
+            "Bramble: Move selected at depth {} after {} positions and {} seconds.",
+            depth,
+            search.nodes,
+            search.start_time.elapsed().as_secs_f32()
+        );
+
+        let stop = match search.stop {
+            Some(Stop::Return) => MonteCarloStop::Return,
+            Some(Stop::Budget) if search.nodes >= search.max_nodes => MonteCarloStop::SimulationCap,
+            Some(Stop::Budget) => MonteCarloStop::Timeout,
+            _ if search.score.abs() > WIN_SCORE - 1000 => MonteCarloStop::Proven,
+            _ => MonteCarloStop::CompleteTree,
+        };
+        main.send(NoughbertMessage::Move(best, stop)).unwrap();
+    }
+}
@@ -0,0 +1,91 @@
+use game::{
+    board::Board,
+    cell::Cell,
+    game::{Game, Turn},
+    value::Value,
+};
+
+/// The value of a won game, before the number of moves taken to win is taken off
+pub const WIN_SCORE: i32 = 1_000_000;
+/// The factor that evaluations in the range -1 to 1 are scaled by
+pub const EVAL_SCALE: f32 = 10_000.0;
+
+/// How much each cell of a board is worth, as the centre and corners lie on more lines
+const POSITION_WEIGHTS: [f32; 9] = [3.0, 2.0, 3.0, 2.0, 4.0, 2.0, 3.0, 2.0, 3.0];
+/// The total of the position weights
+const POSITION_TOTAL: f32 = 24.0;
+/// How much an unfinished sub-board counts towards its cell, compared to a won one
+const SUB_BOARD_WEIGHT: f32 = 0.5;
+/// The value of a line with two of a player's cells and one free cell
+const THREAT_WEIGHT: f32 = 0.15;
+/// The value of being allowed to play anywhere, after the opponent was sent to a finished board
+const FREE_CHOICE_WEIGHT: f32 = 0.1;
+/// The lines of three on a board
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+/// Estimates how good a board is for the first player, from -1 to 1
+fn evaluate_board(board: &Board) -> f32 {
+    match board.check() {
+        Value::Player1 => return 1.0,
+        Value::Player2 => return -1.0,
+        Value::Draw => return 0.0,
+        Value::None => {}
+    }
+
+    // Count the cells (and sub-boards) held, with unfinished sub-boards adding their own estimate
+    let mut material = 0.0;
+    for (cell, weight) in board.cells.iter().zip(POSITION_WEIGHTS) {
+        material += weight
+            * match cell {
+                Cell::Board(b) if b.check() == Value::None => SUB_BOARD_WEIGHT * evaluate_board(b),
+                x => match x.value() {
+                    Value::Player1 => 1.0,
+                    Value::Player2 => -1.0,
+                    _ => 0.0,
+                },
+            };
+    }
+    material /= POSITION_TOTAL;
+
+    // Count the lines that one more cell would complete
+    let values: Vec<Value> = board.cells.iter().map(Cell::value).collect();
+    let mut threats = 0.0;
+    for line in LINES {
+        let line: Vec<Value> = line.iter().map(|x| values[*x]).collect();
+        let free = line.iter().filter(|x| **x == Value::None).count();
+        if free != 1 {
+            continue;
+        }
+        if line.iter().filter(|x| **x == Value::Player1).count() == 2 {
+            threats += THREAT_WEIGHT;
+        } else if line.iter().filter(|x| **x == Value::Player2).count() == 2 {
+            threats -= THREAT_WEIGHT;
+        }
+    }
+
+    (material + threats).clamp(-0.95, 0.95)
+}
+
+/// Estimates how good an unfinished game is for the player to move, scaled by `EVAL_SCALE`
+pub fn evaluate(g: &Game) -> i32 {
+    let mut eval = evaluate_board(&g.board);
+    if g.turn != Turn::Player1 {
+        eval = -eval;
+    }
+
+    // Being sent to a finished board lets the player to move pick any board
+    if g.depth > 1 && g.legal.len() < g.depth - 1 {
+        eval += FREE_CHOICE_WEIGHT;
+    }
+
+    (eval * EVAL_SCALE) as i32
+}
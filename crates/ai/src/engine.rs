@@ -0,0 +1,25 @@
+/// The computer players that may be chosen for each side
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    Noughbert,
+    Thorn,
+    Bramble,
+}
+
+impl Engine {
+    pub fn name(&self) -> String {
+        match self {
+            Engine::Noughbert => "Noughbert".to_owned(),
+            Engine::Thorn => "Thorn".to_owned(),
+            Engine::Bramble => "Bramble".to_owned(),
+        }
+    }
+
+    pub fn next(self) -> Engine {
+        match self {
+            Engine::Noughbert => Self::Thorn,
+            Engine::Thorn => Self::Bramble,
+            Engine::Bramble => Self::Noughbert,
+        }
+    }
+}
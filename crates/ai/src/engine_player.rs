@@ -0,0 +1,75 @@
+use std::{sync::mpsc, thread};
+
+use game::{game::Game, Move};
+
+use crate::{
+    bramble::bramble, comms::Comms, engine::Engine, monte_carlo_settings::MonteCarloSettings,
+    noughbert::noughbert, noughbert_message::NoughbertMessage, thorn::thorn,
+    thorn_message::ThornMessage,
+};
+
+/// The main thread's end of an engine's channel, by the messages the engine understands
+enum Channel {
+    Noughbert(Comms<NoughbertMessage>),
+    Thorn(Comms<ThornMessage>),
+}
+
+/// A computer player running on its own thread, for playing games without the UI
+pub struct EnginePlayer {
+    /// The engine doing the thinking
+    pub engine: Engine,
+    /// The settings for each move, with the game and player to optimise for filled in as it is asked
+    pub settings: MonteCarloSettings,
+    channel: Channel,
+}
+
+impl EnginePlayer {
+    /// Constructor function, spawning the engine's thread
+    pub fn new(engine: Engine, settings: MonteCarloSettings) -> EnginePlayer {
+        let channel = match engine {
+            Engine::Noughbert => Channel::Noughbert(spawn(noughbert)),
+            Engine::Thorn => Channel::Thorn(spawn(thorn)),
+            Engine::Bramble => Channel::Noughbert(spawn(bramble)),
+        };
+        EnginePlayer {
+            engine,
+            settings,
+            channel,
+        }
+    }
+
+    /// Asks the engine for its move in the given position, waiting until it has been chosen
+    pub fn play(&self, g: &Game) -> Option<Move> {
+        let settings = MonteCarloSettings {
+            game: g.clone(),
+            opt_for: g.turn,
+            ..self.settings.clone()
+        };
+        match &self.channel {
+            Channel::Noughbert(c) => {
+                c.send(NoughbertMessage::Start(settings)).unwrap();
+                loop {
+                    if let NoughbertMessage::Move(mv, _) = c.recv().unwrap() {
+                        return mv;
+                    }
+                }
+            }
+            Channel::Thorn(c) => {
+                c.send(ThornMessage::Start(settings)).unwrap();
+                loop {
+                    if let ThornMessage::Move(mv, _) = c.recv().unwrap() {
+                        return mv;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Runs an engine on a new thread, returning the main thread's end of its channel
+fn spawn<T: Send + 'static>(engine: fn(Comms<T>)) -> Comms<T> {
+    let (tx_0, rx_0) = mpsc::sync_channel(0);
+    let (tx_1, rx_1) = mpsc::sync_channel(1);
+    thread::spawn(move || engine(Comms::new(rx_0, tx_1)));
+    Comms::new(rx_1, tx_0)
+}
@@ -1,17 +1,33 @@
-use std::{fmt::{self, Display}, fs, hash::{DefaultHasher, Hash, Hasher}, mem, path::Path, process::Command, ptr::hash};
+use std::{fmt::{self, Display}, fs, hash::{DefaultHasher, Hash, Hasher}, mem, path::Path, process::Command};
 
-use id_tree::{Node, NodeId, Tree};
+use id_tree::{NodeId, Tree};
 
-use crate::{
-    game::{
-        game::{Game, Turn},
-        value::Value,
-    },
-    styles::AUTOCOMPILE_GRAPHVIS_FILES,
+use game::{
+    game::{Game, Turn},
+    value::Value,
 };
 
 use super::monte_carlo_node::MonteCarloNode;
 
+/// An enum representing the possible graphvis debug levels
+#[derive(Debug, PartialEq, Eq)]
+pub enum GraphvisOutputLevel {
+    /// Prints after every recieved playout
+    Full,
+
+    /// Prints after every set of simulations
+    FinalsOnly,
+
+    /// Does not print
+    None,
+}
+/// Enables the logging of tree graphs
+// pub const OUTPUT_GRAPHVIS_FILES: bool = true;
+pub const OUTPUT_GRAPHVIS_FILES: GraphvisOutputLevel = GraphvisOutputLevel::FinalsOnly;
+
+/// Enables the automatic compilation of tree graphs to .svg files
+pub const AUTOCOMPILE_GRAPHVIS_FILES: bool = true;
+
 pub fn output_graphvis_files(
     tree: &Tree<MonteCarloNode>,
     game: &Game,
@@ -151,7 +167,7 @@ impl<'a, T, F: Fn(&Tree<T>, &NodeId) -> String> Display for TreeWrapper<'a, T, F
     fn fmt(&self, w: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(w, "digraph {{")?;
         writeln!(w, "node [shape=box]")?;
-        for node in self.tree.traverse_post_order_ids(self.tree.root_node_id().unwrap()).unwrap() {
+        for node in self.tree.traverse_post_order_ids(self.root).unwrap() {
             let mut hasher = DefaultHasher::new();
             node.hash(&mut hasher);
             let id = hasher.finish();
@@ -0,0 +1,34 @@
+//! The computer players, built on top of the headless `game` rules.
+
+pub mod amaf;
+pub mod book_mode;
+pub mod bramble;
+pub mod bramble_eval;
+pub mod comms;
+pub mod engine;
+pub mod engine_player;
+pub mod thorn;
+pub mod thorn_message;
+pub mod thorn_node;
+pub mod thorn_tree;
+pub mod graphvis;
+pub mod noughbert_message;
+pub mod playout;
+pub mod playout_policy;
+pub mod monte_carlo;
+pub mod monte_carlo_node;
+pub mod monte_carlo_parallelism;
+pub mod monte_carlo_policy;
+pub mod monte_carlo_proof;
+pub mod monte_carlo_settings;
+pub mod monte_carlo_stop;
+pub mod move_thoughts;
+pub mod noughbert;
+pub mod opening_book;
+pub mod root_parallel;
+pub mod simulation_thread;
+pub mod solver;
+pub mod solver_stop;
+pub mod thoughts;
+pub mod transposition_table;
+pub mod worker_pool;
@@ -0,0 +1,844 @@
+use core::panic;
+
+// use ego_tree::{NodeId, Tree};
+use anyhow::{bail, Result};
+use fastrand::Rng;
+use id_tree::{InsertBehavior, MoveBehavior, Node, NodeId, RemoveBehavior, Tree, TreeBuilder};
+
+use game::{
+    game::{Game, Turn},
+    value::Value,
+    Move,
+};
+
+use super::{
+    amaf::{cell_index, AmafMoves},
+    monte_carlo_node::MonteCarloNode, monte_carlo_policy::MonteCarloPolicy,
+    monte_carlo_proof::MonteCarloProof, move_thoughts::MoveThoughts, playout::playout,
+    playout_policy::PlayoutPolicy, thoughts::Thoughts, transposition_table::TranspositionTable,
+};
+
+#[derive(Debug)]
+/// The Monte Carlo manager struct
+pub struct MonteCarloManager {
+    /// The game that is being evaluated
+    pub g: Game,
+    /// The game tree
+    pub tree: Tree<MonteCarloNode>,
+    /// The number of simulations that have been run
+    pub sims: usize,
+    /// The number of simulations that have been started
+    pub sims_requested: usize,
+    /// The player the scores in the tree are relative to
+    pub opt_for: Turn,
+    /// The number of leaves sent out while a simulation of them was already pending
+    pub duplicate_leaves: usize,
+    /// The statistics shared between transposed positions, if enabled
+    pub table: Option<TranspositionTable>,
+    /// How moves are picked during playouts
+    pub playout: PlayoutPolicy,
+    /// The RAVE equivalence parameter, if all-moves-as-first statistics are being used
+    pub rave: Option<f32>,
+    /// The source of the random choices made while searching
+    pub rng: Rng,
+}
+
+impl MonteCarloManager {
+    /// Constructor function
+    pub fn new(g: Game, t: Turn) -> MonteCarloManager {
+        let moves_count = &g.legal_moves().len();
+        let hash = g.hash;
+        MonteCarloManager {
+            g,
+            // tree: Tree::new(MonteCarloNode::new(vec![], *moves_count, !t)),
+            tree: TreeBuilder::new().with_root(Node::new(MonteCarloNode::new(vec![], *moves_count, !t, hash))).build(),
+            sims: 0,
+            sims_requested: 0,
+            opt_for: t,
+            duplicate_leaves: 0,
+            table: None,
+            playout: PlayoutPolicy::Random,
+            rave: None,
+            rng: Rng::new(),
+        }
+    }
+
+    /// Turns the transposition table on or off, filling a new table from the existing tree
+    pub fn set_transpositions(&mut self, enabled: bool) {
+        if !enabled {
+            self.table = None;
+            return;
+        }
+        if self.table.is_some() {
+            return;
+        }
+
+        let mut table = TranspositionTable::new();
+        let root = self.tree.root_node_id().unwrap();
+        for node in self.tree.traverse_pre_order(root).unwrap() {
+            table.add_playouts(node.data().hash, node.data().playouts);
+            table.add_score(node.data().hash, node.data().score);
+        }
+        self.table = Some(table);
+    }
+
+    /// Brings the tree to the given position, keeping the subtree of the moves played since
+    pub fn sync(&mut self, g: &Game) -> Result<()> {
+        let played = self.g.moves.len();
+        if g.depth != self.g.depth
+            || g.moves.len() < played
+            || g.moves[..played] != self.g.moves[..]
+        {
+            bail!("Game does not follow on from the tree")
+        }
+
+        for mv in &g.moves[played..] {
+            self.advance(&mv[0])?;
+        }
+        Ok(())
+    }
+
+    /// Plays a move at the root, keeping the subtree below it and dropping the rest
+    pub fn advance(&mut self, play: &[usize]) -> Result<()> {
+        self.g.play(play)?;
+
+        let root = self.tree.root_node_id().unwrap().clone();
+        let child = self
+            .tree
+            .children_ids(&root)
+            .unwrap()
+            .find(|x| self.tree.get(x).unwrap().data().play == play)
+            .cloned();
+
+        match child {
+            Some(child) => {
+                // Promote the child, then drop the old root along with the other branches
+                self.tree.move_node(&child, MoveBehavior::ToRoot).unwrap();
+                self.tree.remove_node(root, RemoveBehavior::DropChildren).unwrap();
+                // The root's move has already been played on `g`
+                self.tree.get_mut(&child).unwrap().data_mut().play = vec![];
+            }
+            None => {
+                let moves_count = self.g.legal_moves().len();
+                self.tree = TreeBuilder::new()
+                    .with_root(Node::new(MonteCarloNode::new(
+                        vec![],
+                        moves_count,
+                        !self.g.turn,
+                        self.g.hash,
+                    )))
+                    .build();
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds the root statistics of another tree of the same position into this one
+    pub fn merge(&mut self, other: &MonteCarloManager) {
+        let root = self.tree.root_node_id().unwrap().clone();
+        let other_root = other.tree.root_node_id().unwrap();
+
+        for other_child in other.tree.children(other_root).unwrap() {
+            let data = other_child.data();
+            let existing = self
+                .tree
+                .children_ids(&root)
+                .unwrap()
+                .find(|x| self.tree.get(x).unwrap().data().play == data.play)
+                .cloned();
+            let id = match existing {
+                Some(id) => id,
+                None => self
+                    .tree
+                    .insert(
+                        Node::new(MonteCarloNode::new(
+                            data.play.clone(),
+                            data.child_count,
+                            data.turn,
+                            data.hash,
+                        )),
+                        InsertBehavior::UnderNode(&root),
+                    )
+                    .unwrap(),
+            };
+            let node = self.tree.get_mut(&id).unwrap().data_mut();
+            node.playouts += data.playouts;
+            node.score += data.score;
+            node.amaf_playouts += data.amaf_playouts;
+            node.amaf_score += data.amaf_score;
+            if node.proof.is_none() && data.proof.is_some() {
+                node.proof = data.proof;
+                self.propagate_proof(&id);
+            }
+        }
+
+        let other_data = other.tree.get(other_root).unwrap().data();
+        let node = self.tree.get_mut(&root).unwrap().data_mut();
+        node.playouts += other_data.playouts;
+        node.score += other_data.score;
+
+        // The other tree's playouts hold for its positions wherever they are reached
+        if let (Some(table), Some(other_table)) = (&mut self.table, &other.table) {
+            for (hash, entry) in &other_table.entries {
+                table.add_playouts(*hash, entry.playouts);
+                table.add_score(*hash, entry.score);
+            }
+        }
+    }
+
+    /// Negates every score in the tree if it was last searched for the other player
+    pub fn reorient(&mut self, opt_for: Turn) {
+        if self.opt_for == opt_for {
+            return;
+        }
+        let root = self.tree.root_node_id().unwrap().clone();
+        let ids: Vec<_> = self.tree.traverse_pre_order_ids(&root).unwrap().collect();
+        for id in ids {
+            let data = self.tree.get_mut(&id).unwrap().data_mut();
+            data.score = -data.score;
+            data.amaf_score = -data.amaf_score;
+        }
+        if let Some(table) = &mut self.table {
+            for entry in table.entries.values_mut() {
+                entry.score = -entry.score;
+            }
+        }
+        self.opt_for = opt_for;
+    }
+
+    /// Selects the next move for simulation
+    pub fn select(&mut self, exploration_factor: f32, opt_for: Turn) -> Option<&NodeId> {
+        let mut plays = 0;
+        let mut current_node_id = self.tree.root_node_id().unwrap();
+        let mut current_node = self.tree.get(current_node_id).unwrap();
+
+        loop {
+            let chn = current_node.children();
+            let moves = self.g.legal_moves().len();
+
+            // Break if a terminal, proven, or not fully expanded node is selected
+            if current_node.children().len() < moves
+                || current_node.data().child_count == 0
+                || current_node.data().proof.is_some()
+                || self.g.board.check() != Value::None
+            {
+                break;
+            }
+
+            let mut best_node_ids: Vec<&NodeId> = vec![];
+            let mut best_score = f32::MIN;
+
+            // Calculate the child(ren) with the highest UCB1 value
+            for node_id in chn {
+                let node = self.tree.get(node_id).unwrap();
+                let val = node.data();
+                // A proven move has nothing left to learn, and its parent is proven once all are
+                if val.proof.is_some() {
+                    continue;
+                }
+                // Use the statistics of every transposition of the position where they are shared
+                let parent_playouts = current_node.data().playouts;
+                let (playouts, score) = match self.table.as_ref().and_then(|x| x.get(val.hash)) {
+                    Some(entry) => (entry.playouts, entry.score),
+                    None => (val.playouts, val.score),
+                };
+                let ucb1 = val.ucb1_from(
+                    playouts,
+                    score,
+                    exploration_factor,
+                    parent_playouts,
+                    opt_for,
+                    self.rave,
+                );
+                if ucb1 > best_score {
+                    best_node_ids = vec![node_id];
+                    best_score = ucb1;
+                } else if ucb1 == best_score {
+                    best_node_ids.insert(best_node_ids.len(), node_id);
+                } else if ucb1 <= f32::MIN {
+                    eprintln!("Node {:?} has UCB1 of {ucb1}", node_id)
+                }
+            }
+
+            // Choose a random next best node
+            let Some(next_id) = self.rng.choice(best_node_ids) else {
+                break;
+            };
+            current_node_id = next_id;
+            current_node = self.tree.get(current_node_id).unwrap();
+
+            match self.g.play(&current_node.data().play) {
+                Ok(()) => plays += 1,
+                Err(_) => panic!(),
+            }
+        }
+
+        // Unplay each move made
+        for _ in 0..plays {
+            match self.g.unplay() {
+                Ok(()) => {}
+                Err(_) => panic!(),
+            }
+        }
+
+        // Return the selected node
+        Some(current_node_id)
+    }
+
+    /// Adds a child node (where applicable) to the selected node
+    pub fn expand(&mut self, node_id: &NodeId) -> NodeId {
+        let node = self.tree.get(node_id).unwrap();
+
+        // A proven node has nothing left to learn
+        if node.data().proof.is_some() {
+            return node_id.clone();
+        }
+
+        let mut count = 0;
+
+        // Play each move preceding the selected node
+        for x in self.tree.ancestors(node_id).unwrap().collect::<Vec<_>>().iter().rev().skip(1) {
+            match self.g.play(&x.data().play) {
+                Ok(()) => count += 1,
+                Err(_) => panic!(),
+            }
+        }
+
+        // Play the move of the selected node
+        if !node.data().play.is_empty() {
+            match self.g.play(&node.data().play) {
+                Ok(()) => count += 1,
+                Err(_) => panic!(),
+            }
+        }
+
+        // Filter off each move that has not yet been expanded
+        let mut moves = self.g.legal_moves();
+        moves.retain(|x| !node.children().iter().any(|a| &self.tree.get(a).unwrap().data().play == x));
+
+        // If all moves are expanded, or the node is terminal, return the node
+        if moves.is_empty() || self.g.board.check() != Value::None {
+            for _ in 0..count {
+                match self.g.unplay() {
+                    Ok(()) => {}
+                    Err(_) => panic!(),
+                }
+            }
+            return node_id.clone();
+        }
+
+        // Choose a random remaining move and play it
+        let play = self.rng.choice(moves).unwrap();
+        self.g.play(&play).unwrap();
+        count += 1;
+
+        let moves_count = self.g.legal_moves().len();
+        let hash = self.g.hash;
+        let new_turn = !self.tree.get(node_id).unwrap().data().turn;
+
+        // A move that ends the game is proven straight away
+        let proof = match self.g.board.check() {
+            Value::None => None,
+            Value::Draw => Some(MonteCarloProof::Draw),
+            v if v == new_turn.val() => Some(MonteCarloProof::Win(0)),
+            _ => Some(MonteCarloProof::Loss(0)),
+        };
+
+        for _ in 0..count {
+            self.g.unplay().unwrap();
+        }
+
+        // Append the new child and return it
+        // let out = node_mut
+        //     .append(MonteCarloNode {
+        //         play,
+        //         playouts: 0.0,
+        //         score: 0.0,
+        //         child_count: moves_count,
+        //         turn: new_turn,
+        //     })
+        //     .id();
+
+        let child_id = self.tree.insert(Node::new(MonteCarloNode {
+                play,
+                playouts: 0.0,
+                score: 0.0,
+                pending: 0.0,
+                child_count: moves_count,
+                turn: new_turn,
+                proof,
+                hash,
+                amaf_playouts: 0.0,
+                amaf_score: 0.0,
+            }), InsertBehavior::UnderNode(node_id)
+        ).unwrap();
+
+        if proof.is_some() {
+            self.propagate_proof(&child_id);
+        }
+        child_id
+    }
+
+    /// Works out whether the node is proven by its children, returning whether it was newly proven
+    fn update_proof(&mut self, node_id: &NodeId) -> bool {
+        let node = self.tree.get(node_id).unwrap();
+        if node.data().proof.is_some() {
+            return false;
+        }
+
+        let mut all_proven = node.children().len() >= node.data().child_count;
+        let mut quickest_win: Option<usize> = None;
+        let mut slowest_loss: Option<usize> = None;
+        let mut draw = false;
+        for child in node.children() {
+            match self.tree.get(child).unwrap().data().proof {
+                Some(MonteCarloProof::Win(n)) => {
+                    quickest_win = Some(quickest_win.map_or(n, |q| q.min(n)))
+                }
+                Some(MonteCarloProof::Loss(n)) => {
+                    slowest_loss = Some(slowest_loss.map_or(n, |s| s.max(n)))
+                }
+                Some(MonteCarloProof::Draw) => draw = true,
+                None => all_proven = false,
+            }
+        }
+
+        // The opponent takes any win they have, else settles for a draw before a loss
+        let proof = if let Some(n) = quickest_win {
+            Some(MonteCarloProof::Loss(n + 1))
+        } else if !all_proven {
+            None
+        } else if draw {
+            Some(MonteCarloProof::Draw)
+        } else {
+            slowest_loss.map(|n| MonteCarloProof::Win(n + 1))
+        };
+
+        self.tree.get_mut(node_id).unwrap().data_mut().proof = proof;
+        proof.is_some()
+    }
+
+    /// Carries a newly proven node's outcome up the tree for as long as it proves its parents
+    pub fn propagate_proof(&mut self, node_id: &NodeId) {
+        let ancestors: Vec<_> = self.tree.ancestor_ids(node_id).unwrap().cloned().collect();
+        for ancestor in ancestors {
+            if !self.update_proof(&ancestor) {
+                break;
+            }
+        }
+    }
+
+    /// Returns the proven outcome of the root, for the player who made its move
+    pub fn root_proof(&self) -> Option<MonteCarloProof> {
+        self.tree
+            .get(self.tree.root_node_id().unwrap())
+            .unwrap()
+            .data()
+            .proof
+    }
+
+    /// Returns the proven value of the node relative to `opt_for`, if it has been proven
+    pub fn proven_value(&self, node_id: &NodeId, opt_for: Turn) -> Option<f32> {
+        self.tree.get(node_id).unwrap().data().proven_value(opt_for)
+    }
+
+    /// Returns the statistics of the search so far, relative to `t`
+    pub fn thoughts(&self, t: Turn) -> Thoughts {
+        let root_id = self.tree.root_node_id().unwrap();
+        let root = self.tree.get(root_id).unwrap().data();
+        // Scores are kept relative to `opt_for`, so flip them for the other player
+        let flip = if t == self.opt_for { 1.0 } else { -1.0 };
+        // Proofs are for the player who made the node's move
+        let proof_for = |node: &MonteCarloNode| {
+            node.proof.map(|p| if node.turn == t { p } else { p.flip() })
+        };
+
+        let mut moves: Vec<MoveThoughts> = self
+            .tree
+            .children_ids(root_id)
+            .unwrap()
+            .map(|id| {
+                let node = self.tree.get(id).unwrap().data();
+                MoveThoughts {
+                    play: node.play.clone(),
+                    sims: node.playouts as usize,
+                    mean: if node.playouts > 0.0 {
+                        flip * node.score / node.playouts
+                    } else {
+                        0.0
+                    },
+                    proven: proof_for(node),
+                    pv: self.principal_variation(id),
+                }
+            })
+            .collect();
+        moves.sort_by_key(|x| std::cmp::Reverse(x.sims));
+
+        Thoughts {
+            sims: root.playouts as usize,
+            score: flip * root.score,
+            proven: proof_for(root),
+            moves,
+            started: self.sims_requested,
+            duplicate_leaves: self.duplicate_leaves,
+        }
+    }
+
+    /// Returns the moves expected to follow a node, taking the most simulated child each time
+    fn principal_variation(&self, node_id: &NodeId) -> Vec<Move> {
+        let mut pv = vec![];
+        let mut node_id = node_id;
+        while let Some(child) = self
+            .tree
+            .children_ids(node_id)
+            .unwrap()
+            .filter(|x| self.tree.get(x).unwrap().data().playouts > 0.0)
+            .max_by(|a, b| {
+                let a = self.tree.get(a).unwrap().data().playouts;
+                let b = self.tree.get(b).unwrap().data().playouts;
+                a.total_cmp(&b)
+            })
+        {
+            pv.push(self.tree.get(child).unwrap().data().play.clone());
+            node_id = child;
+        }
+        pv
+    }
+
+    /// Returns the playouts of the two most simulated moves from the root
+    pub fn top_two_playouts(&self) -> (f32, f32) {
+        let mut top = (0.0, 0.0);
+        for child in self.tree.children(self.tree.root_node_id().unwrap()).unwrap() {
+            let playouts = child.data().playouts;
+            if playouts > top.0 {
+                top = (playouts, top.0);
+            } else if playouts > top.1 {
+                top.1 = playouts;
+            }
+        }
+        top
+    }
+
+    /// Runs a playout on the selected node
+    pub fn simulate<'a>(&mut self, node_id: &'a NodeId, opt_for: Turn) -> (&'a NodeId, f32) {
+        // A proven node's result is already known
+        if let Some(v) = self.proven_value(node_id, opt_for) {
+            return (node_id, v);
+        }
+
+        let node = self.tree.get(node_id).unwrap();
+
+        // Play each move preceding the selected node
+        let mut count = 0;
+        for x in self.tree.ancestors(node_id).unwrap().collect::<Vec<_>>().iter().rev() {
+            if !x.data().play.is_empty() {
+                let x = &x.data();
+                match self.g.play(&x.play) {
+                    Ok(()) => count += 1,
+                    Err(_) => panic!(),
+                }
+            }
+        }
+
+        // Play the move of the selected node
+        match self.g.play(&node.data().play) {
+            Ok(()) => count += 1,
+            Err(_) => panic!(),
+        }
+
+        // Repeatedly play moves until a terminal state is reached
+        let result = playout(&self.g, opt_for, self.playout, self.rave.is_some(), &mut self.rng);
+
+        // Unplay all moves made
+        for _ in 0..count {
+            self.g.unplay().unwrap();
+        }
+
+        // Record every move made for the RAVE statistics here, as callers only see the value
+        if let Some(amaf) = result.amaf {
+            self.backpropogate_amaf(node_id, result.value, amaf);
+        }
+
+        // Return the node, and the simulation result
+        (node_id, result.value)
+    }
+
+    /// Runs a whole simulation on this thread, from selection through to backpropagation.
+    /// Returns its result, or None if there was no node left to select
+    pub fn step(&mut self, exploration_factor: f32, opt_for: Turn) -> Option<f32> {
+        let x = self.select(exploration_factor, opt_for).cloned()?;
+        let x = self.expand(&x);
+        let (x, val) = self.simulate(&x, opt_for);
+        self.backpropogate_playouts(x, 1.0);
+        self.backpropogate_value(x, val);
+        self.sims += 1;
+        Some(val)
+    }
+
+    /// Runs up to `sims` simulations on this thread, stopping early once the root is proven
+    pub fn search(&mut self, sims: usize, exploration_factor: f32, opt_for: Turn) {
+        for _ in 0..sims {
+            if self.root_proof().is_some() || self.step(exploration_factor, opt_for).is_none() {
+                break;
+            }
+        }
+    }
+
+    /// Returns the game as it would be after the moves leading to the node
+    pub fn game_at(&self, node_id: &NodeId) -> Game {
+        let mut game = self.g.clone();
+        let node = self.tree.get(node_id).unwrap();
+        for x in self.tree.ancestors(node_id).unwrap().collect::<Vec<_>>().iter().rev() {
+            if !x.data().play.is_empty() {
+                game.play(&x.data().play).unwrap();
+            }
+        }
+        if !node.data().play.is_empty() {
+            game.play(&node.data().play).unwrap();
+        }
+        game
+    }
+
+    /// Propagates the value up the tree
+    pub fn backpropogate_value(&mut self, node_id: &NodeId, val: f32) {
+        // Apply result to the leaf node
+        let node_mut = self.tree.get_mut(node_id).unwrap();
+        node_mut.data_mut().score += val;
+        if let Some(table) = &mut self.table {
+            table.add_score(node_mut.data().hash, val);
+        }
+
+        // Loop over each parent node of the selected node
+        let ancestors: Vec<_> = self.tree.ancestor_ids(node_id).unwrap().cloned().collect();
+        for ancestor in ancestors.iter().rev() {
+            // Adjust the value of the parent node
+            let anode = self.tree.get_mut(ancestor).unwrap();
+            anode.data_mut().score += val;
+            if let Some(table) = &mut self.table {
+                table.add_score(anode.data().hash, val);
+            }
+        }
+    }
+
+    /// Propagates the value up the tree
+    pub fn backpropogate_playouts(&mut self, node_id: &NodeId, val: f32) {
+        // Apply result to the leaf node
+
+        let node_mut = self.tree.get_mut(node_id).unwrap();
+        node_mut.data_mut().playouts += val;
+        if let Some(table) = &mut self.table {
+            table.add_playouts(node_mut.data().hash, val);
+        }
+
+        // Loop over each parent node of the selected node
+        let ancestors: Vec<_> = self.tree.ancestor_ids(node_id).unwrap().cloned().collect();
+        for ancestor in ancestors.iter().rev() {
+            // Adjust the value of the parent node
+            let anode = self.tree.get_mut(ancestor).unwrap();
+            anode.data_mut().playouts += val;
+            if let Some(table) = &mut self.table {
+                table.add_playouts(anode.data().hash, val);
+            }
+        }
+    }
+
+    /// Adds a simulation to the all-moves-as-first statistics of every child along the path to the node
+    /// whose move was made later in the simulation by the same player
+    pub fn backpropogate_amaf(&mut self, node_id: &NodeId, val: f32, mut amaf: AmafMoves) {
+        // The moves leading to the node were made during the simulation too
+        let mut path: Vec<NodeId> = self.tree.ancestor_ids(node_id).unwrap().cloned().collect();
+        path.insert(0, node_id.clone());
+        for id in &path {
+            let data = self.tree.get(id).unwrap().data();
+            if !data.play.is_empty() {
+                amaf.insert(cell_index(&data.play), data.turn);
+            }
+        }
+
+        // Cells played before a node are taken, so can never match one of its children
+        for id in &path {
+            let children: Vec<NodeId> = self.tree.children_ids(id).unwrap().cloned().collect();
+            for child in children {
+                let data = self.tree.get_mut(&child).unwrap().data_mut();
+                if amaf.contains(cell_index(&data.play), data.turn) {
+                    data.amaf_playouts += 1.0;
+                    data.amaf_score += val;
+                }
+            }
+        }
+    }
+
+    /// Marks a simulation through the node as started or finished, depending on the sign of `val`
+    pub fn backpropogate_pending(&mut self, node_id: &NodeId, val: f32) {
+        // Count the leaf as a duplicate if it is already being played out
+        let node_mut = self.tree.get_mut(node_id).unwrap();
+        if val > 0.0 && node_mut.data().pending > 0.0 {
+            self.duplicate_leaves += 1;
+        }
+        node_mut.data_mut().pending += val;
+
+        // Loop over each parent node of the selected node
+        let ancestors: Vec<_> = self.tree.ancestor_ids(node_id).unwrap().cloned().collect();
+        for ancestor in ancestors.iter().rev() {
+            let anode = self.tree.get_mut(ancestor).unwrap();
+            anode.data_mut().pending += val;
+        }
+    }
+
+    /// Returns the root's children, leaving out those proven to lose unless every one is
+    fn candidates(&self) -> Vec<&NodeId> {
+        let root = self.tree.root_node_id().unwrap();
+        let children: Vec<&NodeId> = self.tree.children_ids(root).unwrap().collect();
+        let not_losing: Vec<&NodeId> = children
+            .iter()
+            .copied()
+            .filter(|x| {
+                !matches!(
+                    self.tree.get(x).unwrap().data().proof,
+                    Some(MonteCarloProof::Loss(_))
+                )
+            })
+            .collect();
+        if not_losing.is_empty() {
+            children
+        } else {
+            not_losing
+        }
+    }
+
+    /// Return the best move based on the selected policy
+    pub fn best(
+        &mut self,
+        policy: MonteCarloPolicy,
+        opt_for: Turn,
+        _exploration_factor: f32,
+    ) -> Option<Move> {
+        // Always take a proven win, choosing the quickest
+        let mut quickest_win = None;
+        for child in self.tree.children(self.tree.root_node_id().unwrap()).unwrap() {
+            if let Some(MonteCarloProof::Win(n)) = child.data().proof {
+                if quickest_win.as_ref().is_none_or(|(q, _)| n < *q) {
+                    quickest_win = Some((n, child.data().play.clone()));
+                }
+            }
+        }
+        if let Some((_, play)) = quickest_win {
+            return Some(play);
+        }
+
+        match policy {
+            MonteCarloPolicy::Robust => {
+                let mut best_score = 0.0;
+                let mut best_id = None;
+
+                for child in self.candidates() {
+                    let cnode = self.tree.get(child).unwrap();
+                    if cnode.data().playouts > best_score {
+                        best_score = cnode.data().playouts;
+                        best_id = Some(child);
+                    }
+                }
+
+                if let Some(id) = best_id {
+                    Some(self.tree.get(id).unwrap().data().play.clone())
+                } else {
+                    self.rng.choice(self.g.legal_moves())
+                }
+            }
+            MonteCarloPolicy::Maximum => {
+                let mut best_score = f32::NEG_INFINITY;
+                let mut best_id = None;
+
+                for child in self.candidates() {
+                    let cnode = self.tree.get(child).unwrap();
+                    if cnode.data().score(opt_for) >= best_score {
+                        best_score = cnode.data().score(opt_for);
+                        best_id = Some(child);
+                    }
+                }
+
+                if let Some(id) = best_id {
+                    Some(self.tree.get(id).unwrap().data().play.clone())
+                } else {
+                    self.rng.choice(self.g.legal_moves())
+                }
+            }
+            MonteCarloPolicy::Frail => {
+                let mut worst_score = f32::MAX;
+                let mut worst_id = None;
+
+                for child in self.candidates() {
+                    let cnode = self.tree.get(child).unwrap();
+                    if cnode.data().playouts < worst_score {
+                        worst_score = cnode.data().playouts;
+                        worst_id = Some(child);
+                    }
+                }
+
+                if let Some(id) = worst_id {
+                    Some(self.tree.get(id).unwrap().data().play.clone())
+                } else {
+                    self.rng.choice(self.g.legal_moves())
+                }
+            }
+            MonteCarloPolicy::Minimum => {
+                let mut worst_score = f32::INFINITY;
+                let mut worst_id = None;
+
+                for child in self.candidates() {
+                    let cnode = self.tree.get(child).unwrap();
+                    if cnode.data().score(opt_for) <= worst_score {
+                        worst_score = cnode.data().score(opt_for);
+                        worst_id = Some(child);
+                    }
+                }
+
+                if let Some(id) = worst_id {
+                    Some(self.tree.get(id).unwrap().data().play.clone())
+                } else {
+                    self.rng.choice(self.g.legal_moves())
+                }
+            }
+            MonteCarloPolicy::Random => {
+                // Avoid moves proven to lose, unless there is nothing else
+                let losing: Vec<Move> = self
+                    .tree
+                    .children(self.tree.root_node_id().unwrap())
+                    .unwrap()
+                    .filter(|x| matches!(x.data().proof, Some(MonteCarloProof::Loss(_))))
+                    .map(|x| x.data().play.clone())
+                    .collect();
+                let mut moves = self.g.legal_moves();
+                moves.retain(|x| !losing.contains(x));
+                if moves.is_empty() {
+                    moves = self.g.legal_moves();
+                }
+                self.rng.choice(moves)
+            }
+            // Don't use ever
+            MonteCarloPolicy::UCB1 => {
+                let mut best_ucb1 = f32::NEG_INFINITY;
+                let mut best_id = None;
+
+                for child in self.candidates() {
+                    let cnode = self.tree.get(child).unwrap();
+                    // Children without playouts have no mean to compare
+                    if cnode.data().playouts == 0.0 {
+                        continue;
+                    }
+                    let ucb1 = cnode.data().score(opt_for) / cnode.data().playouts;
+                    if ucb1 >= best_ucb1 {
+                        best_ucb1 = ucb1;
+                        best_id = Some(child);
+                    }
+                }
+
+                if let Some(id) = best_id {
+                    Some(self.tree.get(id).unwrap().data().play.clone())
+                } else {
+                    self.rng.choice(self.g.legal_moves())
+                }
+            }
+        }
+    }
+}
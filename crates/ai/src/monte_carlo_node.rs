@@ -0,0 +1,116 @@
+use game::{game::Turn, Move};
+
+use super::monte_carlo_proof::MonteCarloProof;
+
+/// The score each pending simulation is assumed to have lost by while selecting
+pub const VIRTUAL_LOSS: f32 = 1.0;
+
+#[derive(Debug, PartialEq)]
+pub struct MonteCarloNode {
+    /// The move that the node represents
+    pub play: Move,
+    /// The number of simulations where this move was made
+    pub playouts: f32,
+    /// The score of simulations
+    pub score: f32,
+    /// The number of simulations through this node that are still being played out
+    pub pending: f32,
+    /// The number of children that node will have once fully expanded
+    pub child_count: usize,
+    /// The turn for which the node's move is
+    pub turn: Turn,
+    /// The outcome of the node, once it has been proven
+    pub proof: Option<MonteCarloProof>,
+    /// The hash of the position after the node's move
+    pub hash: u64,
+    /// The number of simulations below the node's parent where this move was made at any point
+    pub amaf_playouts: f32,
+    /// The score of those simulations
+    pub amaf_score: f32,
+}
+
+impl MonteCarloNode {
+    /// Constructor function
+    pub fn new(play: Move, child_count: usize, turn: Turn, hash: u64) -> MonteCarloNode {
+        MonteCarloNode {
+            play,
+            playouts: 0.0,
+            score: 0.0,
+            pending: 0.0,
+            child_count,
+            turn,
+            proof: None,
+            hash,
+            amaf_playouts: 0.0,
+            amaf_score: 0.0,
+        }
+    }
+
+    /// Calculates the UCB1 value for the node
+    pub fn ucb1(&self, exploration_factor: f32, parent_playouts: f32, opt_for: Turn) -> f32 {
+        self.ucb1_from(
+            self.playouts,
+            self.score,
+            exploration_factor,
+            parent_playouts,
+            opt_for,
+            None,
+        )
+    }
+
+    /// Calculates the UCB1 value for the node from the given statistics, such as those shared between transpositions.
+    ///
+    /// If a RAVE equivalence is given, the mean is blended with the all-moves-as-first mean, which is
+    /// weighted equally once the node has that many playouts and fades out after
+    pub fn ucb1_from(
+        &self,
+        playouts: f32,
+        score: f32,
+        exploration_factor: f32,
+        parent_playouts: f32,
+        opt_for: Turn,
+        rave: Option<f32>,
+    ) -> f32 {
+        let flip = if self.turn == opt_for { 1.0 } else { -1.0 };
+        // eprintln!(
+        //     "({} / {}) + sqrt(ln({}) * {} / {} ) = {}",
+        //     self.score(opt_for),
+        //     self.playouts,
+        //     parent_playouts, exploration_factor, self.playouts,
+        //     (self.score(opt_for) / self.playouts)
+        //         + (parent_playouts.ln() * exploration_factor / self.playouts).sqrt()
+        // );
+        // Pending simulations count as losses, steering other selections elsewhere until they return
+        let mut mean = (flip * score - VIRTUAL_LOSS * self.pending) / playouts;
+        if let Some(k) = rave {
+            if self.amaf_playouts > 0.0 {
+                let beta = (k / (3.0 * playouts + k)).sqrt();
+                mean = (1.0 - beta) * mean + beta * flip * self.amaf_score / self.amaf_playouts;
+            }
+        }
+        mean + (parent_playouts.ln() * exploration_factor / playouts).sqrt()
+    }
+
+    /// Calculates the relative score of the node based on the turn of the node
+    pub fn score(&self, opt_for: Turn) -> f32 {
+        if self.turn == opt_for {
+            self.score
+        } else {
+            -self.score
+        }
+    }
+
+    /// Returns the proven value of the node relative to `opt_for`, if it has been proven
+    pub fn proven_value(&self, opt_for: Turn) -> Option<f32> {
+        let proof = if self.turn == opt_for {
+            self.proof?
+        } else {
+            self.proof?.flip()
+        };
+        Some(match proof {
+            MonteCarloProof::Win(_) => 1.0,
+            MonteCarloProof::Draw => 0.0,
+            MonteCarloProof::Loss(_) => -1.0,
+        })
+    }
+}
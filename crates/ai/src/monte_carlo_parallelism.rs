@@ -0,0 +1,24 @@
+/// The ways in which a search may be spread over several threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonteCarloParallelism {
+    /// One shared tree, with the playouts of its leaves run by a pool of workers
+    Tree,
+    /// An independent tree per thread, with the root statistics merged at the end
+    Root,
+}
+
+impl MonteCarloParallelism {
+    pub fn name(&self) -> String {
+        match self {
+            MonteCarloParallelism::Tree => "Tree".to_owned(),
+            MonteCarloParallelism::Root => "Root".to_owned(),
+        }
+    }
+
+    pub fn next(self) -> MonteCarloParallelism {
+        match self {
+            MonteCarloParallelism::Tree => Self::Root,
+            MonteCarloParallelism::Root => Self::Tree,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+#[derive(Debug, Clone, Copy)]
+pub enum MonteCarloPolicy {
+    /// Largest number of simulations
+    Robust,
+    /// Highest total value
+    Maximum,
+    /// Lowest number of simulations
+    Frail,
+    /// Lowest total value
+    Minimum,
+    /// A random move
+    Random,
+    /// Highest UCB1 value
+    UCB1,
+}
+
+impl MonteCarloPolicy {
+    pub fn name(&self) -> String {
+        match self {
+            MonteCarloPolicy::Robust => "Robust".to_owned(),
+            MonteCarloPolicy::Maximum => "Maximum".to_owned(),
+            MonteCarloPolicy::Frail => "Frail".to_owned(),
+            MonteCarloPolicy::Minimum => "Minimum".to_owned(),
+            MonteCarloPolicy::Random => "Random".to_owned(),
+            MonteCarloPolicy::UCB1 => "UCB1".to_owned(),
+        }
+    }
+
+    pub fn next(self) -> MonteCarloPolicy {
+        match self {
+            MonteCarloPolicy::Robust => Self::Maximum,
+            MonteCarloPolicy::Maximum => Self::Frail,
+            MonteCarloPolicy::Frail => Self::Minimum,
+            MonteCarloPolicy::Minimum => Self::Random,
+            MonteCarloPolicy::Random => Self::UCB1,
+            MonteCarloPolicy::UCB1 => Self::Robust,
+        }
+    }
+}
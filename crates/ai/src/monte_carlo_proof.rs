@@ -0,0 +1,21 @@
+/// The outcome of a node that has been proven, for the player who made the node's move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonteCarloProof {
+    /// A forced win, ending in the given number of further moves
+    Win(usize),
+    /// A forced draw
+    Draw,
+    /// A forced loss, ending in the given number of further moves
+    Loss(usize),
+}
+
+impl MonteCarloProof {
+    /// Returns the same outcome from the point of view of the other player
+    pub fn flip(self) -> MonteCarloProof {
+        match self {
+            MonteCarloProof::Win(n) => MonteCarloProof::Loss(n),
+            MonteCarloProof::Draw => MonteCarloProof::Draw,
+            MonteCarloProof::Loss(n) => MonteCarloProof::Win(n),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use game::game::{Game, Turn};
+
+use super::{
+    book_mode::BookMode, monte_carlo_parallelism::MonteCarloParallelism,
+    monte_carlo_policy::MonteCarloPolicy, playout_policy::PlayoutPolicy,
+};
+
+#[derive(Debug, Clone)]
+/// A struct to govern the settings of the AI
+pub struct MonteCarloSettings {
+    /// The game that is being evaluated
+    pub game: Game,
+    /// The maximum time allowed for calculation
+    pub timeout: Duration,
+    /// The maximum number of simulations allowed for calculation
+    pub max_sims: usize,
+    /// The maximum number of threads allowed for calculation
+    pub threads: usize,
+    /// The exploration factor for the UCB1 algorithm
+    pub exploration_factor: f32,
+    /// The player for which the move should be optimised
+    pub opt_for: Turn,
+    /// Whether the tree should be kept and re-rooted between moves
+    pub carry_forward: bool,
+    /// The policy with which the move should be selected
+    pub policy: MonteCarloPolicy,
+    /// How the search should be spread over the threads
+    pub parallelism: MonteCarloParallelism,
+    /// Whether statistics should be shared between transposed positions
+    pub transpositions: bool,
+    /// How moves should be picked during playouts
+    pub playout: PlayoutPolicy,
+    /// The RAVE equivalence parameter: the number of playouts at which a move's all-moves-as-first
+    /// statistics count as much as its own. RAVE is off if this is `None`
+    pub rave: Option<f32>,
+    /// Whether to keep searching, ignoring the time and simulation limits, until interrupted. No
+    /// move is returned unless one is asked for
+    pub endless: bool,
+    /// The seed for the search's random choices, so that a single-threaded search with the same
+    /// limits can be repeated exactly. A fresh seed is used if this is `None`
+    pub seed: Option<u64>,
+    /// How moves should be taken from the opening book, for positions that are in it
+    pub book: BookMode,
+    /// Whether positions small enough to solve exhaustively should be played perfectly. Larger
+    /// positions are searched as usual
+    pub perfect: bool,
+}
@@ -0,0 +1,35 @@
+/// Why a search stopped and returned its move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MonteCarloStop {
+    /// A move was asked to be returned early
+    Return,
+    /// The result of the position is known
+    Proven,
+    /// The simulation limit was reached
+    SimulationCap,
+    /// The time limit was reached
+    Timeout,
+    /// There was nothing left to search
+    CompleteTree,
+    /// The best move could not be overtaken in the remaining budget
+    Decided,
+    /// There was only one legal move
+    OnlyMove,
+    /// The move was taken from the opening book
+    Book,
+}
+
+impl MonteCarloStop {
+    pub fn name(&self) -> String {
+        match self {
+            MonteCarloStop::Return => "Return request".to_owned(),
+            MonteCarloStop::Proven => "Proven result".to_owned(),
+            MonteCarloStop::SimulationCap => "Simulation cap".to_owned(),
+            MonteCarloStop::Timeout => "Timeout".to_owned(),
+            MonteCarloStop::CompleteTree => "Complete game tree".to_owned(),
+            MonteCarloStop::Decided => "Best move decided".to_owned(),
+            MonteCarloStop::OnlyMove => "Only legal move".to_owned(),
+            MonteCarloStop::Book => "Opening book".to_owned(),
+        }
+    }
+}
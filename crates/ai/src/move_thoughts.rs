@@ -0,0 +1,22 @@
+use game::Move;
+
+use crate::monte_carlo_proof::MonteCarloProof;
+
+/// The statistics of one of the moves available in the position being searched
+#[derive(Debug, Clone)]
+pub struct MoveThoughts {
+    /// The move
+    pub play: Move,
+
+    /// Number of simulations carried out through the move
+    pub sims: usize,
+
+    /// Mean score of those simulations, from -1 to 1, relative to the requested turn
+    pub mean: f32,
+
+    /// The proven outcome of the move for the requested turn, if known
+    pub proven: Option<MonteCarloProof>,
+
+    /// The expected continuation after the move, following the most simulated reply each time
+    pub pv: Vec<Move>,
+}
@@ -0,0 +1,444 @@
+use std::{fs, sync::mpsc, time};
+
+use fastrand::Rng;
+use id_tree::NodeId;
+
+use game::value::Value;
+
+use crate::{
+    book_mode::BookMode,
+    graphvis::{output_graphvis_files, GraphvisOutputLevel, OUTPUT_GRAPHVIS_FILES},
+    monte_carlo::MonteCarloManager,
+    monte_carlo_parallelism::MonteCarloParallelism,
+    monte_carlo_policy::MonteCarloPolicy,
+    monte_carlo_stop::MonteCarloStop,
+    noughbert_message::NoughbertMessage,
+    opening_book::{opening_book_path, OpeningBook},
+    playout::PlayoutResult,
+    root_parallel::root_parallel,
+    solver::{Solver, SOLVER_MAX_NODES, SOLVER_TIME_FRACTION},
+    solver_stop::SolverStop,
+    worker_pool::{WorkerPool, BATCH_SIZE},
+};
+
+use super::comms::Comms;
+
+/// How many simulations run between checks on whether the search can stop early
+const STOP_CHECK_INTERVAL: usize = 256;
+/// How close, as a fraction of the best move's playouts, the second best must be to earn more time
+const CLOSE_RATIO: f32 = 0.8;
+/// How far the time limit may be stretched when the top two moves are close
+const MAX_TIME_EXTENSION: f32 = 1.5;
+
+/// Applies a returned playout to the tree, or withdraws it if its worker crashed
+fn apply_result(noughbert: &mut MonteCarloManager, id: &NodeId, v: Option<PlayoutResult>) {
+    match v {
+        Some(v) => {
+            noughbert.sims += 1;
+            noughbert.backpropogate_value(id, v.value);
+            if let Some(amaf) = v.amaf {
+                noughbert.backpropogate_amaf(id, v.value, amaf);
+            }
+        }
+        None => {
+            noughbert.sims_requested -= 1;
+            noughbert.backpropogate_playouts(id, -1.0);
+        }
+    }
+    noughbert.backpropogate_pending(id, -1.0);
+}
+
+/// Answers requests for thoughts until the main thread interrupts or asks for a move, returning which
+fn idle(noughbert: &MonteCarloManager, main: &Comms<NoughbertMessage>) -> (bool, bool) {
+    loop {
+        match main.recv().unwrap() {
+            NoughbertMessage::Interrupt => return (true, false),
+            NoughbertMessage::Return() => return (false, true),
+            NoughbertMessage::GetThoughts(t) => {
+                main.send(NoughbertMessage::Thoughts(noughbert.thoughts(t)))
+                    .unwrap();
+            }
+            NoughbertMessage::Start(_) => {}
+            NoughbertMessage::Thoughts(_) => {}
+            NoughbertMessage::Move(..) => {}
+        }
+    }
+}
+
+pub fn noughbert(main: Comms<NoughbertMessage>) {
+    // Count the number of AI simulations
+    let mut runs = 0;
+    // let mut graphviz_prints = 0;
+    // The tree kept from the last search, if it is being carried forward
+    let mut carried: Option<MonteCarloManager> = None;
+    // The simulation threads, kept alive between searches
+    let mut pool = WorkerPool::new(0);
+    // The opening book, which is empty if there isn't one to read
+    let book_path = opening_book_path();
+    let book = match OpeningBook::load(&book_path) {
+        Ok(book) => {
+            println!(
+                "Loaded {} positions from the opening book at {}",
+                book.len(),
+                book_path.display()
+            );
+            book
+        }
+        Err(e) => {
+            println!("No opening book loaded: {e:#}");
+            OpeningBook::default()
+        }
+    };
+
+    // Clear and re-create the `./outs` folder
+    if OUTPUT_GRAPHVIS_FILES != GraphvisOutputLevel::None {
+        let _ = fs::remove_dir_all("./outs");
+        let _ = fs::create_dir("./outs");
+    }
+
+    loop {
+        // Recieve all messages, if a `Message::Start()` is recieved, begin simulation
+        // Stop once the main thread has gone
+        let Ok(message) = main.recv() else {
+            return;
+        };
+        let mc_options = match message {
+            NoughbertMessage::Start(x) => x,
+            NoughbertMessage::Interrupt => continue,
+            NoughbertMessage::GetThoughts(_) => continue,
+            NoughbertMessage::Thoughts(_) => continue,
+            NoughbertMessage::Move(..) => continue,
+            NoughbertMessage::Return() => continue,
+        };
+
+        println!("Simulation requested");
+
+        // Answer straight away from the opening book where it can, keeping any carried tree for later
+        if mc_options.book != BookMode::Off && !mc_options.endless {
+            let mut rng = mc_options.seed.map_or_else(Rng::new, Rng::with_seed);
+            if let Some(play) = book.choose(&mc_options.game, mc_options.book, &mut rng) {
+                println!("Exited due to opening book");
+                main.send(NoughbertMessage::Move(Some(play), MonteCarloStop::Book))
+                    .unwrap();
+                continue;
+            }
+        }
+
+        // Re-root the previous tree on the current position where possible, else start afresh
+        let mut noughbert = match carried.take() {
+            Some(mut m) if mc_options.carry_forward => {
+                if m.sync(&mc_options.game).is_ok() {
+                    m.reorient(mc_options.opt_for);
+                    m.sims = 0;
+                    m.sims_requested = 0;
+                    m.duplicate_leaves = 0;
+                    m
+                } else {
+                    MonteCarloManager::new(mc_options.game.clone(), mc_options.opt_for)
+                }
+            }
+            _ => MonteCarloManager::new(mc_options.game.clone(), mc_options.opt_for),
+        };
+        noughbert.set_transpositions(mc_options.transpositions);
+        noughbert.playout = mc_options.playout;
+        noughbert.rave = mc_options.rave;
+        noughbert.rng = mc_options.seed.map_or_else(Rng::new, Rng::with_seed);
+        let reused = noughbert
+            .tree
+            .get(noughbert.tree.root_node_id().unwrap())
+            .unwrap()
+            .data()
+            .playouts;
+        let start_time = time::Instant::now();
+        // An endless search keeps one tree, so that its statistics can be reported per move
+        let root_mode = mc_options.parallelism == MonteCarloParallelism::Root
+            && mc_options.threads > 1
+            && !mc_options.endless;
+        // A single thread runs the playouts itself rather than handing them off
+        if !root_mode {
+            pool.resize(if mc_options.threads == 1 {
+                0
+            } else {
+                mc_options.threads
+            });
+        }
+        let mut interrupt = false;
+        let mut interrupt_return = false;
+        let mut prints_this_run = 0;
+        // The reason for the search stopping, once it is known
+        let mut stop: Option<MonteCarloStop> = None;
+        // The time limit, which may be stretched while the top two moves are close
+        let mut timeout = mc_options.timeout;
+        let mut next_check = STOP_CHECK_INTERVAL;
+
+        // Make sure a move is never requested on a completed board state
+        // #TODO: Test code
+        assert_eq!(noughbert.g.board.check(), Value::None);
+
+        if noughbert.g.board.check() != Value::None {
+            interrupt = true;
+        }
+
+        // With only one move to make there is nothing to search
+        if !mc_options.endless && noughbert.g.legal_moves().len() == 1 {
+            stop = Some(MonteCarloStop::OnlyMove);
+        }
+
+        // Play perfectly in positions small enough to solve, searching as usual with the time left
+        // if the solver runs out
+        let mut solved = None;
+        if mc_options.perfect
+            && !mc_options.endless
+            && !interrupt
+            && stop.is_none()
+            && Solver::can_solve(&noughbert.g)
+        {
+            let mut solver = Solver::new(Some(SOLVER_MAX_NODES));
+            solver.deadline = Some(start_time + mc_options.timeout.mul_f32(SOLVER_TIME_FRACTION));
+            solver.main = Some(&main);
+            solved = solver.best(&mc_options.game);
+            match solver.stop {
+                None => {
+                    println!("Solved the position in {} nodes", solver.nodes);
+                    stop = Some(MonteCarloStop::Proven);
+                }
+                Some(SolverStop::Budget) => {
+                    println!(
+                        "Position unsolved after {} nodes, searching instead",
+                        solver.nodes
+                    );
+                }
+                Some(SolverStop::Return) => {
+                    interrupt_return = true;
+                    stop = Some(MonteCarloStop::Return);
+                }
+                Some(SolverStop::Interrupt) => interrupt = true,
+            }
+        }
+
+        // Search a separate tree on each thread, merging them back into this one
+        if root_mode && !interrupt && stop.is_none() {
+            (interrupt, interrupt_return) = root_parallel(&mut noughbert, &mc_options, &main);
+        }
+
+        // Start new iteration within current bounds
+        while !interrupt && !root_mode && stop.is_none() {
+            if noughbert.root_proof().is_some() {
+                stop = Some(MonteCarloStop::Proven);
+                break;
+            }
+            if !mc_options.endless {
+                if noughbert.sims >= mc_options.max_sims {
+                    stop = Some(MonteCarloStop::SimulationCap);
+                    break;
+                }
+                let elapsed = start_time.elapsed();
+                let (best, second) = noughbert.top_two_playouts();
+                if elapsed >= timeout {
+                    // Give a close decision more time, up to a limit
+                    let extended = mc_options.timeout.mul_f32(MAX_TIME_EXTENSION);
+                    if timeout < extended && second >= best * CLOSE_RATIO {
+                        timeout = extended;
+                    } else {
+                        stop = Some(MonteCarloStop::Timeout);
+                        break;
+                    }
+                }
+                // The most simulated move is played, so stop once it can no longer be caught
+                if noughbert.sims >= next_check {
+                    next_check = noughbert.sims + STOP_CHECK_INTERVAL;
+                    let rate = noughbert.sims as f32 / elapsed.as_secs_f32();
+                    let remaining = ((mc_options.max_sims - noughbert.sims) as f32)
+                        .min(rate * timeout.saturating_sub(elapsed).as_secs_f32());
+                    if matches!(mc_options.policy, MonteCarloPolicy::Robust)
+                        && best - second > remaining
+                    {
+                        stop = Some(MonteCarloStop::Decided);
+                        break;
+                    }
+                }
+            }
+
+            // Recieve all messages; Break if interrupted
+            let message = main.try_recv();
+            match message {
+                Ok(m) => match m {
+                    NoughbertMessage::Start(_) => {}
+                    NoughbertMessage::Interrupt => {
+                        interrupt = true;
+                        break;
+                    }
+                    NoughbertMessage::GetThoughts(t) => {
+                        main.send(NoughbertMessage::Thoughts(noughbert.thoughts(t)))
+                            .unwrap();
+                    }
+                    NoughbertMessage::Thoughts(_) => {}
+                    NoughbertMessage::Move(..) => {}
+                    NoughbertMessage::Return() => {
+                        interrupt_return = true;
+                        break;
+                    }
+                },
+                Err(e) => match e {
+                    mpsc::TryRecvError::Empty => {}
+                    mpsc::TryRecvError::Disconnected => panic!("Thread disconnected"),
+                },
+            }
+
+            // Apply the results of any finished batches
+            for (id, v) in pool.collect(false) {
+                apply_result(&mut noughbert, &id, v);
+            }
+
+            if mc_options.threads == 1 {
+                // Run the MCTS algorithm once
+                let x = noughbert
+                    .select(mc_options.exploration_factor, mc_options.opt_for)
+                    .cloned();
+                if x.is_none() {
+                    stop = Some(MonteCarloStop::CompleteTree);
+                    break;
+                }
+                let x = noughbert.expand(&x.unwrap());
+                let (x, val) = noughbert.simulate(&x, mc_options.opt_for);
+                noughbert.backpropogate_playouts(x, 1.0);
+                noughbert.backpropogate_value(x, val);
+                // Increment the number of simulations run
+                noughbert.sims += 1;
+                noughbert.sims_requested += 1;
+                continue;
+            }
+
+            // Give every idle worker a batch, else as many leaves as there are sims remaining
+            for worker in pool.idle() {
+                let batch_size = if mc_options.endless {
+                    BATCH_SIZE
+                } else {
+                    BATCH_SIZE.min(mc_options.max_sims - noughbert.sims_requested)
+                };
+                let mut leaves = Vec::with_capacity(batch_size);
+                for _ in 0..batch_size {
+                    if noughbert.root_proof().is_some() {
+                        break;
+                    }
+                    let x = noughbert
+                        .select(mc_options.exploration_factor, mc_options.opt_for)
+                        .cloned();
+                    let Some(x) = x else {
+                        break;
+                    };
+                    let x = noughbert.expand(&x);
+                    // Proven leaves need no playout, so score them straight away
+                    if let Some(v) = noughbert.proven_value(&x, mc_options.opt_for) {
+                        noughbert.backpropogate_playouts(&x, 1.0);
+                        noughbert.backpropogate_value(&x, v);
+                        noughbert.sims += 1;
+                        noughbert.sims_requested += 1;
+                        continue;
+                    }
+                    noughbert.backpropogate_playouts(&x, 1.0);
+                    noughbert.backpropogate_pending(&x, 1.0);
+                    leaves.push((x.clone(), noughbert.game_at(&x)));
+                    noughbert.sims_requested += 1;
+                }
+                if leaves.is_empty() {
+                    break;
+                }
+                pool.dispatch(
+                    worker,
+                    leaves,
+                    mc_options.opt_for,
+                    mc_options.playout,
+                    mc_options.rave.is_some(),
+                    noughbert.rng.u64(..),
+                );
+
+                prints_this_run += 1;
+                if OUTPUT_GRAPHVIS_FILES == GraphvisOutputLevel::Full {
+                    output_graphvis_files(
+                        &noughbert.tree,
+                        &noughbert.g,
+                        &format!("Run{}Print{prints_this_run}", runs + 1),
+                        mc_options.exploration_factor,
+                        mc_options.opt_for,
+                    );
+                }
+            }
+        }
+
+        // Wait for the batches still being played out, so that none of their results are lost
+        for (id, v) in pool.collect(true) {
+            apply_result(&mut noughbert, &id, v);
+        }
+
+        // An endless search that has run out of tree keeps answering until it is stopped
+        if mc_options.endless && !interrupt && !interrupt_return {
+            (interrupt, interrupt_return) = idle(&noughbert, &main);
+        }
+
+        // Work out and report the reason for the cycle ending
+        if interrupt {
+            println!("Exited due to interrupt request");
+            if mc_options.carry_forward {
+                carried = Some(noughbert);
+            }
+            continue;
+        }
+        let stop = if interrupt_return {
+            MonteCarloStop::Return
+        } else if let Some(stop) = stop {
+            stop
+        } else if noughbert.root_proof().is_some() {
+            MonteCarloStop::Proven
+        } else if noughbert.sims >= mc_options.max_sims {
+            MonteCarloStop::SimulationCap
+        } else if start_time.elapsed() >= mc_options.timeout {
+            MonteCarloStop::Timeout
+        } else {
+            MonteCarloStop::CompleteTree
+        };
+        println!("Exited due to {}", stop.name().to_lowercase());
+        println!(
+            "Move selected after {} sims ({} carried forward) and {} seconds.",
+            noughbert.sims,
+            reused,
+            start_time.elapsed().as_secs_f32()
+        );
+        println!(
+            "{} of {} leaves were dispatched while already pending.",
+            noughbert.duplicate_leaves, noughbert.sims_requested
+        );
+
+        // Calculate the best play, which is forced if there is only one
+        let best_play = if solved.is_some() {
+            solved
+        } else if stop == MonteCarloStop::OnlyMove {
+            noughbert.g.legal_moves().pop()
+        } else {
+            noughbert.best(
+                mc_options.policy,
+                mc_options.opt_for,
+                mc_options.exploration_factor,
+            )
+        };
+
+        // Send the best move calculated and increment the runs counter
+        main.send(NoughbertMessage::Move(best_play, stop)).unwrap();
+        runs += 1;
+
+        // If needed, output the node `.svg` files
+        if OUTPUT_GRAPHVIS_FILES != GraphvisOutputLevel::None {
+            output_graphvis_files(
+                &noughbert.tree,
+                &noughbert.g,
+                &format!("Run{runs}Final"),
+                mc_options.exploration_factor,
+                mc_options.opt_for,
+            );
+        }
+
+        if mc_options.carry_forward {
+            carried = Some(noughbert);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use id_tree::NodeId;
+
+use game::{
+    game::{Game, Turn},
+    Move,
+};
+
+use super::{
+    monte_carlo_settings::MonteCarloSettings, monte_carlo_stop::MonteCarloStop,
+    playout::PlayoutResult, playout_policy::PlayoutPolicy, thoughts::Thoughts,
+};
+
+/// Defines the messages that may be passed between the main and Monte Carlo threads
+pub enum NoughbertMessage {
+    /// Requests that the thread begin simulating
+    Start(MonteCarloSettings),
+
+    /// Requests that the Monte Carlo thread return a move
+    Return(),
+
+    /// Requests the information on the current gamestate
+    GetThoughts(Turn),
+
+    /// Returns the information on the current gamestate
+    Thoughts(Thoughts),
+
+    /// Sends a move, and why the search for it stopped
+    Move(Option<Move>, MonteCarloStop),
+
+    /// Stops the calculation of a move
+    Interrupt,
+}
+
+/// Defines the messages that may be passed between the Monte Carlo thread and its workers
+pub enum ExplorationRequest {
+    /// Requests that the worker shut down
+    Stop,
+
+    /// Requests that the worker play out each leaf, seeding its random choices with `seed`
+    Batch {
+        leaves: Vec<(NodeId, Game)>,
+        opt_for: Turn,
+        playout: PlayoutPolicy,
+        rave: bool,
+        seed: u64,
+    },
+
+    /// Returns the result of each leaf in a batch
+    Return {
+        results: Vec<(NodeId, PlayoutResult)>,
+    },
+}
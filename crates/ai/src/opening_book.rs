@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use fastrand::Rng;
+use serde::{Deserialize, Serialize};
+
+use game::{game::Game, Move};
+
+use crate::book_mode::BookMode;
+
+/// The name of the opening book's file
+pub const OPENING_BOOK_FILE: &str = "opening_book.json";
+/// The environment variable that, if set, gives the path of the opening book instead
+pub const OPENING_BOOK_VAR: &str = "NOUGHBERT_BOOK";
+/// The fraction of the best move's simulations a move needs to be picked by `BookMode::Varied`
+pub const BOOK_VARIETY_RATIO: f32 = 0.2;
+
+/// Returns where the opening book is kept: the path in `OPENING_BOOK_VAR` if it is set, else beside
+/// the executable, so that the book is found whichever directory the game is started from
+pub fn opening_book_path() -> PathBuf {
+    if let Some(path) = env::var_os(OPENING_BOOK_VAR) {
+        return PathBuf::from(path);
+    }
+    env::current_exe()
+        .ok()
+        .and_then(|x| x.parent().map(|dir| dir.join(OPENING_BOOK_FILE)))
+        .unwrap_or_else(|| PathBuf::from(OPENING_BOOK_FILE))
+}
+
+/// The statistics of a move from a position in the book
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookMove {
+    /// The move
+    pub play: Move,
+    /// The number of simulations carried out through the move
+    pub sims: usize,
+    /// The mean score of those simulations, from -1 to 1, for the player making the move
+    pub mean: f32,
+}
+
+/// A position in the book, as it is stored in the file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BookEntry {
+    depth: usize,
+    hash: u64,
+    moves: Vec<BookMove>,
+}
+
+/// The results of long searches of early positions, so that they needn't be searched again
+#[derive(Debug, Clone, Default)]
+pub struct OpeningBook {
+    /// The moves from each position, keyed by the depth of the board and the position's hash
+    positions: HashMap<(usize, u64), Vec<BookMove>>,
+}
+
+impl OpeningBook {
+    /// Reads a book from a file
+    pub fn load(path: &Path) -> Result<OpeningBook> {
+        let bytes = fs::read(path).with_context(|| format!("Couldn't read {}", path.display()))?;
+        let entries: Vec<BookEntry> = serde_json::from_slice(&bytes)
+            .with_context(|| format!("Couldn't parse {}", path.display()))?;
+        Ok(OpeningBook {
+            positions: entries
+                .into_iter()
+                .map(|x| ((x.depth, x.hash), x.moves))
+                .collect(),
+        })
+    }
+
+    /// Writes the book to a file, with the positions in a stable order
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let mut entries: Vec<BookEntry> = self
+            .positions
+            .iter()
+            .map(|(&(depth, hash), moves)| BookEntry {
+                depth,
+                hash,
+                moves: moves.clone(),
+            })
+            .collect();
+        entries.sort_by_key(|x| (x.depth, x.hash));
+        fs::write(path, serde_json::to_string_pretty(&entries)?)?;
+        Ok(())
+    }
+
+    /// Returns the number of positions in the book
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns whether the book has no positions
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the moves from a position, if it is in the book
+    pub fn get(&self, g: &Game) -> Option<&[BookMove]> {
+        self.positions.get(&(g.depth, g.hash)).map(|x| x.as_slice())
+    }
+
+    /// Adds a position to the book, replacing any moves it already had
+    pub fn insert(&mut self, g: &Game, moves: Vec<BookMove>) {
+        self.positions.insert((g.depth, g.hash), moves);
+    }
+
+    /// Picks a move for the position as `mode` asks, if the position is in the book
+    pub fn choose(&self, g: &Game, mode: BookMode, rng: &mut Rng) -> Option<Move> {
+        // Only moves that are legal here are trusted, in case of a clash between hashes
+        let legal = g.legal_moves();
+        let moves: Vec<&BookMove> = self
+            .get(g)?
+            .iter()
+            .filter(|x| x.sims > 0 && legal.contains(&x.play))
+            .collect();
+        let best = moves.iter().max_by_key(|x| x.sims)?;
+
+        match mode {
+            BookMode::Off => None,
+            BookMode::Best => Some(best.play.clone()),
+            BookMode::Varied => {
+                let min_sims = best.sims as f32 * BOOK_VARIETY_RATIO;
+                let candidates: Vec<&&BookMove> =
+                    moves.iter().filter(|x| x.sims as f32 >= min_sims).collect();
+                // Pick a move with a probability in proportion to its simulations
+                let total: usize = candidates.iter().map(|x| x.sims).sum();
+                let mut pick = rng.usize(..total);
+                for x in candidates {
+                    if pick < x.sims {
+                        return Some(x.play.clone());
+                    }
+                    pick -= x.sims;
+                }
+                Some(best.play.clone())
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use fastrand::Rng;
+use game::{
+    bitboard::{BitMove, Bitboard, MAX_MOVES},
+    game::{Game, Turn},
+    value::Value,
+};
+
+use crate::{
+    amaf::{cell_index, AmafMoves},
+    playout_policy::{PlayoutPolicy, PlayoutWeights},
+};
+
+/// The result of a simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayoutResult {
+    /// The score of the finished game for the player being optimised for
+    pub value: f32,
+    /// The moves made during the playout, if they were recorded for RAVE
+    pub amaf: Option<AmafMoves>,
+}
+
+/// Returns the score of a finished game for `opt_for`
+fn result(value: Value, opt_for: Turn) -> f32 {
+    if value == opt_for.val() {
+        1.0
+    } else if value == Value::Draw {
+        0.0
+    } else {
+        -1.0
+    }
+}
+
+/// Plays moves picked by `policy` until the game is over, returning the result for `opt_for`.
+///
+/// Uses a `Bitboard` where the depth allows, falling back to random moves on the `Game` itself otherwise.
+/// The moves made are recorded if `rave` is set
+pub fn playout(
+    game: &Game,
+    opt_for: Turn,
+    policy: PlayoutPolicy,
+    rave: bool,
+    rng: &mut Rng,
+) -> PlayoutResult {
+    let mut amaf = rave.then(|| AmafMoves::new(game.depth));
+    let value = match (Bitboard::from_game(game), policy) {
+        (Ok(b), PlayoutPolicy::Random) => bitboard_playout(b, opt_for, amaf.as_mut(), rng),
+        (Ok(b), PlayoutPolicy::Heavy(weights)) => {
+            heavy_playout(b, opt_for, weights, amaf.as_mut(), rng)
+        }
+        (Err(_), _) => game_playout(game.clone(), opt_for, amaf.as_mut(), rng),
+    };
+    PlayoutResult { value, amaf }
+}
+
+/// Plays random moves on a `Game` until it is over, returning the result for `opt_for`
+pub fn game_playout(
+    mut game: Game,
+    opt_for: Turn,
+    mut amaf: Option<&mut AmafMoves>,
+    rng: &mut Rng,
+) -> f32 {
+    while game.board.check() == Value::None {
+        let mv = rng.choice(game.legal_moves()).unwrap();
+        if let Some(amaf) = amaf.as_mut() {
+            amaf.insert(cell_index(&mv), game.turn);
+        }
+        game.play(&mv).unwrap();
+    }
+    result(game.board.check(), opt_for)
+}
+
+/// Plays random moves on a `Bitboard` until it is over, returning the result for `opt_for`
+pub fn bitboard_playout(
+    mut b: Bitboard,
+    opt_for: Turn,
+    mut amaf: Option<&mut AmafMoves>,
+    rng: &mut Rng,
+) -> f32 {
+    let mut moves = [BitMove::default(); MAX_MOVES];
+    loop {
+        let count = b.legal_moves(&mut moves);
+        if count == 0 {
+            break;
+        }
+        let mv = moves[rng.usize(..count)];
+        if let Some(amaf) = amaf.as_mut() {
+            amaf.insert(b.cell_index(mv), b.turn);
+        }
+        b.play(mv);
+    }
+    result(b.check(), opt_for)
+}
+
+/// Plays moves on a `Bitboard`, weighted by the rules in `weights`, until it is over, returning the result for `opt_for`
+pub fn heavy_playout(
+    mut b: Bitboard,
+    opt_for: Turn,
+    weights: PlayoutWeights,
+    mut amaf: Option<&mut AmafMoves>,
+    rng: &mut Rng,
+) -> f32 {
+    let mut moves = [BitMove::default(); MAX_MOVES];
+    let mut odds = [0.0f32; MAX_MOVES];
+    loop {
+        let count = b.legal_moves(&mut moves);
+        if count == 0 {
+            break;
+        }
+
+        let mut total = 0.0;
+        for i in 0..count {
+            let mv = moves[i];
+            let mut weight = 1.0;
+            if b.wins_board(mv, b.turn) {
+                weight += weights.win;
+            }
+            if b.wins_board(mv, !b.turn) {
+                weight += weights.block;
+            }
+            if weights.free_choice != 0.0 {
+                let mut next = b.clone();
+                next.play(mv);
+                if next.free_choice() {
+                    weight /= 1.0 + weights.free_choice;
+                }
+            }
+            odds[i] = weight;
+            total += weight;
+        }
+
+        // Pick a move with a probability in proportion to its weight
+        let mut pick = rng.f32() * total;
+        let mut choice = count - 1;
+        for (i, weight) in odds[..count].iter().enumerate() {
+            if pick < *weight {
+                choice = i;
+                break;
+            }
+            pick -= weight;
+        }
+        if let Some(amaf) = amaf.as_mut() {
+            amaf.insert(b.cell_index(moves[choice]), b.turn);
+        }
+        b.play(moves[choice]);
+    }
+    result(b.check(), opt_for)
+}
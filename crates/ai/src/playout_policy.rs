@@ -0,0 +1,47 @@
+/// How strongly each rule of a heavy playout favours or avoids a move.
+///
+/// Every move starts with a weight of 1, and a weight of 0 turns a rule off
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayoutWeights {
+    /// Added for a move that wins its sub-board
+    pub win: f32,
+    /// Added for a move that takes the cell the opponent needs to win its sub-board
+    pub block: f32,
+    /// Divides the weight (plus one) of a move that lets the opponent play in any board
+    pub free_choice: f32,
+}
+
+impl Default for PlayoutWeights {
+    fn default() -> Self {
+        PlayoutWeights {
+            win: 8.0,
+            block: 4.0,
+            free_choice: 3.0,
+        }
+    }
+}
+
+/// The ways in which moves may be picked during a playout
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlayoutPolicy {
+    /// Every legal move is equally likely
+    Random,
+    /// Moves are weighted by simple rules. Only games of up to `MAX_BITBOARD_DEPTH` are played this way
+    Heavy(PlayoutWeights),
+}
+
+impl PlayoutPolicy {
+    pub fn name(&self) -> String {
+        match self {
+            PlayoutPolicy::Random => "Random".to_owned(),
+            PlayoutPolicy::Heavy(_) => "Heavy".to_owned(),
+        }
+    }
+
+    pub fn next(self) -> PlayoutPolicy {
+        match self {
+            PlayoutPolicy::Random => Self::Heavy(PlayoutWeights::default()),
+            PlayoutPolicy::Heavy(_) => Self::Random,
+        }
+    }
+}
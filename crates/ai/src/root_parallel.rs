@@ -0,0 +1,140 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    comms::Comms, monte_carlo::MonteCarloManager, monte_carlo_settings::MonteCarloSettings,
+    noughbert_message::NoughbertMessage, thoughts::Thoughts,
+};
+
+/// Runs plain MCTS on a single tree until it is out of time or simulations, or told to stop
+fn search(
+    tree: &mut MonteCarloManager,
+    mc_options: &MonteCarloSettings,
+    max_sims: usize,
+    start_time: Instant,
+    stop: &AtomicBool,
+    sims: &AtomicUsize,
+    score: &AtomicIsize,
+) {
+    while start_time.elapsed() < mc_options.timeout
+        && tree.sims < max_sims
+        && !stop.load(Ordering::Relaxed)
+    {
+        let Some(val) = tree.step(mc_options.exploration_factor, mc_options.opt_for) else {
+            break;
+        };
+
+        // Once any tree has proven the result, there is nothing left to search for
+        if tree.root_proof().is_some() {
+            stop.store(true, Ordering::Relaxed);
+        }
+
+        // Keep running totals for any thoughts requested mid-search
+        sims.fetch_add(1, Ordering::Relaxed);
+        score.fetch_add(val as isize, Ordering::Relaxed);
+    }
+}
+
+/// Searches `noughbert` alongside an independent tree per extra thread, then merges their root statistics into it.
+///
+/// Returns whether the search was interrupted, and whether a move was asked to be returned early
+pub fn root_parallel(
+    noughbert: &mut MonteCarloManager,
+    mc_options: &MonteCarloSettings,
+    main: &Comms<NoughbertMessage>,
+) -> (bool, bool) {
+    let start_time = Instant::now();
+    let stop = AtomicBool::new(false);
+    let sims = AtomicUsize::new(0);
+    let score = AtomicIsize::new(0);
+    let mut interrupt = false;
+    let mut interrupt_return = false;
+
+    // Share the simulations evenly between the trees
+    let max_sims = mc_options.max_sims.div_ceil(mc_options.threads);
+    let mut others: Vec<MonteCarloManager> = (1..mc_options.threads)
+        .map(|_| {
+            let mut tree = MonteCarloManager::new(noughbert.g.clone(), mc_options.opt_for);
+            tree.set_transpositions(mc_options.transpositions);
+            tree.playout = mc_options.playout;
+            tree.rave = mc_options.rave;
+            // Derive each tree's seed from the main one, so that they search differently
+            tree.rng = fastrand::Rng::with_seed(noughbert.rng.u64(..));
+            tree
+        })
+        .collect();
+
+    // Remember the carried forward statistics, as the tree is lent out during the search
+    let root = noughbert
+        .tree
+        .get(noughbert.tree.root_node_id().unwrap())
+        .unwrap()
+        .data();
+    let (base_playouts, base_score) = (root.playouts, root.score);
+
+    thread::scope(|s| {
+        let handles: Vec<_> = std::iter::once(&mut *noughbert)
+            .chain(others.iter_mut())
+            .map(|tree| {
+                let (stop, sims, score) = (&stop, &sims, &score);
+                s.spawn(move || search(tree, mc_options, max_sims, start_time, stop, sims, score))
+            })
+            .collect();
+
+        // Answer the main thread until every tree has finished
+        while !handles.iter().all(|h| h.is_finished()) {
+            match main.try_recv() {
+                Ok(m) => match m {
+                    NoughbertMessage::Interrupt => {
+                        interrupt = true;
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    NoughbertMessage::Return() => {
+                        interrupt_return = true;
+                        stop.store(true, Ordering::Relaxed);
+                    }
+                    NoughbertMessage::GetThoughts(t) => {
+                        // Scores are kept relative to `opt_for`, so flip them for the other player
+                        let total = base_score + score.load(Ordering::Relaxed) as f32;
+                        main.send(NoughbertMessage::Thoughts(Thoughts {
+                            sims: base_playouts as usize + sims.load(Ordering::Relaxed),
+                            score: if t == mc_options.opt_for {
+                                total
+                            } else {
+                                -total
+                            },
+                            // The trees are busy, so any proof is only known once they are merged
+                            proven: None,
+                            moves: vec![],
+                            // Each tree is searched by a single thread, so no leaf is shared
+                            started: sims.load(Ordering::Relaxed),
+                            duplicate_leaves: 0,
+                        }))
+                        .unwrap();
+                    }
+                    NoughbertMessage::Start(_) => {}
+                    NoughbertMessage::Thoughts(_) => {}
+                    NoughbertMessage::Move(..) => {}
+                },
+                Err(e) => match e {
+                    mpsc::TryRecvError::Empty => thread::sleep(Duration::from_millis(1)),
+                    mpsc::TryRecvError::Disconnected => panic!("Thread disconnected"),
+                },
+            }
+        }
+    });
+
+    for other in &others {
+        noughbert.merge(other);
+    }
+    noughbert.sims = sims.load(Ordering::Relaxed);
+    noughbert.sims_requested = noughbert.sims;
+
+    (interrupt, interrupt_return)
+}
@@ -0,0 +1,35 @@
+use fastrand::Rng;
+
+use super::{comms::Comms, noughbert_message::ExplorationRequest, playout::playout};
+
+/// A long-lived worker that plays out each batch of leaves it is sent
+pub fn simulation_thread(noughbert: Comms<ExplorationRequest>) {
+    loop {
+        let (leaves, opt_for, policy, rave, seed) = match noughbert.recv() {
+            Ok(ExplorationRequest::Batch {
+                leaves,
+                opt_for,
+                playout,
+                rave,
+                seed,
+            }) => (leaves, opt_for, playout, rave, seed),
+            Ok(ExplorationRequest::Return { results: _ }) => continue,
+            // Stop when asked to, or when the pool has been dropped
+            Ok(ExplorationRequest::Stop) | Err(_) => return,
+        };
+
+        // Each batch brings its own seed, so the results don't depend on which worker it went to
+        let mut rng = Rng::with_seed(seed);
+        let results = leaves
+            .into_iter()
+            .map(|(id, game)| (id, playout(&game, opt_for, policy, rave, &mut rng)))
+            .collect();
+
+        if noughbert
+            .send(ExplorationRequest::Return { results })
+            .is_err()
+        {
+            return;
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::{collections::HashMap, sync::mpsc, time::Instant};
+
+use game::{game::Game, value::Value, Move};
+
+use crate::{
+    comms::Comms, monte_carlo_proof::MonteCarloProof, noughbert_message::NoughbertMessage,
+    solver_stop::SolverStop,
+};
+
+/// The most positions Noughbert's solver searches before giving up and searching as usual
+pub const SOLVER_MAX_NODES: usize = 500_000;
+/// The most open cells a position may have for Noughbert to try solving it. Depth 2 positions this
+/// small take up to a few hundred thousand positions, and larger ones often millions
+pub const SOLVER_MAX_OPEN_CELLS: usize = 14;
+/// The fraction of the time limit Noughbert's solver may use, leaving the rest for the search
+pub const SOLVER_TIME_FRACTION: f32 = 0.5;
+/// The number of positions searched between checks of the clock and the main thread
+const POLL_INTERVAL: usize = 1024;
+
+/// An exhaustive perfect-play solver, for positions small enough to search to the end.
+///
+/// Outcomes are given as `MonteCarloProof`s, so that they can be checked against the MCTS-Solver's
+#[derive(Default)]
+pub struct Solver<'a> {
+    /// The outcome of each position solved for the player to move, keyed by the depth of the board
+    /// and the position's hash
+    table: HashMap<(usize, u64), MonteCarloProof>,
+    /// The number of positions searched so far
+    pub nodes: usize,
+    /// The most positions to search before giving up, if there is a limit
+    pub max_nodes: Option<usize>,
+    /// The time to give up at, if there is one
+    pub deadline: Option<Instant>,
+    /// The main thread, whose requests to stop are answered while solving
+    pub main: Option<&'a Comms<NoughbertMessage>>,
+    /// Why the solver gave up, if it did
+    pub stop: Option<SolverStop>,
+}
+
+impl<'a> Solver<'a> {
+    /// Constructor function
+    pub fn new(max_nodes: Option<usize>) -> Solver<'a> {
+        Solver {
+            table: HashMap::new(),
+            nodes: 0,
+            max_nodes,
+            deadline: None,
+            main: None,
+            stop: None,
+        }
+    }
+
+    /// Returns whether a position has few enough open cells for Noughbert to try solving it
+    pub fn can_solve(g: &Game) -> bool {
+        g.board.legal_moves(&[]).len() <= SOLVER_MAX_OPEN_CELLS
+    }
+
+    /// Returns the outcome of the position with perfect play for the player to move, or `None` if
+    /// it couldn't be solved within the limit
+    pub fn solve(&mut self, g: &Game) -> Option<MonteCarloProof> {
+        self.search(&mut g.clone())
+    }
+
+    /// Returns the outcome of each legal move with perfect play for the player making it, or `None`
+    /// if they couldn't all be solved within the limit
+    pub fn solve_moves(&mut self, g: &Game) -> Option<Vec<(Move, MonteCarloProof)>> {
+        if g.board.check() != Value::None {
+            return Some(vec![]);
+        }
+        let mut g = g.clone();
+        let mut moves = vec![];
+        for play in g.legal_moves() {
+            g.play(&play).unwrap();
+            let outcome = self.search(&mut g);
+            g.unplay().unwrap();
+            moves.push((play, outcome?.flip()));
+        }
+        Some(moves)
+    }
+
+    /// Returns the quickest winning move, else a drawing move, else the slowest losing move
+    pub fn best(&mut self, g: &Game) -> Option<Move> {
+        self.solve_moves(g)?
+            .into_iter()
+            .max_by_key(|(_, x)| rank(*x))
+            .map(|(play, _)| play)
+    }
+
+    /// Solves the position for the player to move, playing and unplaying moves on `g`
+    fn search(&mut self, g: &mut Game) -> Option<MonteCarloProof> {
+        match g.board.check() {
+            Value::None => {}
+            Value::Draw => return Some(MonteCarloProof::Draw),
+            // Only the player who just moved can have won
+            _ => return Some(MonteCarloProof::Loss(0)),
+        }
+        let key = (g.depth, g.hash);
+        if let Some(&outcome) = self.table.get(&key) {
+            return Some(outcome);
+        }
+        if self.nodes.is_multiple_of(POLL_INTERVAL) {
+            self.poll();
+        }
+        if self.max_nodes.is_some_and(|x| self.nodes >= x) {
+            self.stop.get_or_insert(SolverStop::Budget);
+        }
+        if self.stop.is_some() {
+            return None;
+        }
+        self.nodes += 1;
+
+        // A move's outcome for its player is the opposite of the outcome for the opponent after it
+        let mut best: Option<MonteCarloProof> = None;
+        for play in g.legal_moves() {
+            g.play(&play).unwrap();
+            let outcome = self.search(g);
+            g.unplay().unwrap();
+            let outcome = outcome?.flip();
+            if best.is_none_or(|x| rank(outcome) > rank(x)) {
+                best = Some(outcome);
+            }
+        }
+
+        // The position's outcome is that of its best move, one move further away
+        let outcome = match best.unwrap() {
+            MonteCarloProof::Win(n) => MonteCarloProof::Win(n + 1),
+            MonteCarloProof::Draw => MonteCarloProof::Draw,
+            MonteCarloProof::Loss(n) => MonteCarloProof::Loss(n + 1),
+        };
+        self.table.insert(key, outcome);
+        Some(outcome)
+    }
+
+    /// Checks the clock and the main thread, noting why the solver should stop if it should
+    fn poll(&mut self) {
+        if self.deadline.is_some_and(|x| Instant::now() >= x) {
+            self.stop.get_or_insert(SolverStop::Budget);
+        }
+        let Some(main) = self.main else {
+            return;
+        };
+        match main.try_recv() {
+            Ok(m) => match m {
+                NoughbertMessage::Interrupt => {
+                    self.stop = Some(SolverStop::Interrupt);
+                }
+                NoughbertMessage::Return() => {
+                    self.stop.get_or_insert(SolverStop::Return);
+                }
+                // The thoughts are reported once the search starts, so requests until then are dropped
+                NoughbertMessage::GetThoughts(_) => {}
+                NoughbertMessage::Start(_) => {}
+                NoughbertMessage::Thoughts(_) => {}
+                NoughbertMessage::Move(..) => {}
+            },
+            Err(e) => match e {
+                mpsc::TryRecvError::Empty => {}
+                mpsc::TryRecvError::Disconnected => panic!("Thread disconnected"),
+            },
+        }
+    }
+}
+
+/// Orders outcomes from worst to best for the player they are for: slower wins and quicker losses
+/// are worse
+fn rank(outcome: MonteCarloProof) -> (u8, isize) {
+    match outcome {
+        MonteCarloProof::Loss(n) => (0, n as isize),
+        MonteCarloProof::Draw => (1, 0),
+        MonteCarloProof::Win(n) => (2, -(n as isize)),
+    }
+}
@@ -0,0 +1,10 @@
+/// Why the solver gave up before solving a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolverStop {
+    /// The position or time limit ran out
+    Budget,
+    /// The main thread asked for a move straight away
+    Return,
+    /// The main thread no longer wants a move
+    Interrupt,
+}
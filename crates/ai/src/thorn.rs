@@ -0,0 +1,131 @@
+use std::{sync::mpsc, time};
+
+use fastrand::Rng;
+use game::Move;
+
+use crate::{monte_carlo_stop::MonteCarloStop, thorn_message::ThornMessage, thorn_tree::ThornTree};
+
+use super::comms::Comms;
+
+/// Re-roots the tree on a move played in the game, dropping it if the move doesn't follow on
+fn clip_tree(tree: &mut Option<ThornTree>, play: &Move) {
+    if let Some(t) = tree {
+        if t.advance(play).is_err() {
+            *tree = None;
+        }
+    }
+}
+
+/// Thorn: a single-threaded Monte Carlo engine that keeps its tree between moves
+pub fn thorn(main: Comms<ThornMessage>) {
+    let mut tree: Option<ThornTree> = None;
+
+    loop {
+        // Recieve all messages, if a `ThornMessage::Start()` is recieved, begin searching
+        // Stop once the main thread has gone
+        let Ok(message) = main.recv() else {
+            return;
+        };
+        let options = match message {
+            ThornMessage::Start(x) => x,
+            ThornMessage::Clip(play) => {
+                clip_tree(&mut tree, &play);
+                continue;
+            }
+            ThornMessage::Interrupt => continue,
+            ThornMessage::Return() => continue,
+            ThornMessage::GetThoughts(_) => continue,
+            ThornMessage::Thoughts(_) => continue,
+            ThornMessage::Move(..) => continue,
+        };
+
+        println!("Thorn: Search requested");
+
+        // Reuse as much of the previous tree as still applies
+        let mut t = match tree.take() {
+            Some(mut t) => {
+                t.sync(&options.game);
+                t
+            }
+            None => ThornTree::new(options.game.clone()),
+        };
+        t.rng = options.seed.map_or_else(Rng::new, Rng::with_seed);
+        let reused = t.root().visits;
+
+        let start_time = time::Instant::now();
+        let mut sims = 0;
+        let mut interrupt = false;
+        let mut interrupt_return = false;
+        let mut clip = None;
+
+        // An endless search only stops when it is told to
+        while options.endless || (start_time.elapsed() < options.timeout && sims < options.max_sims)
+        {
+            // Recieve all messages; Break if interrupted
+            match main.try_recv() {
+                Ok(m) => match m {
+                    ThornMessage::Start(_) => {}
+                    ThornMessage::Interrupt => {
+                        interrupt = true;
+                        break;
+                    }
+                    ThornMessage::Return() => {
+                        interrupt_return = true;
+                        break;
+                    }
+                    ThornMessage::Clip(play) => {
+                        clip = Some(play);
+                        break;
+                    }
+                    ThornMessage::GetThoughts(turn) => {
+                        main.send(ThornMessage::Thoughts(t.thoughts(turn))).unwrap();
+                    }
+                    ThornMessage::Thoughts(_) => {}
+                    ThornMessage::Move(..) => {}
+                },
+                Err(e) => match e {
+                    mpsc::TryRecvError::Empty => {}
+                    mpsc::TryRecvError::Disconnected => panic!("Thread disconnected"),
+                },
+            }
+
+            t.iterate(options.exploration_factor);
+            sims += 1;
+        }
+
+        // A move played mid-search means this search is no longer wanted
+        if let Some(play) = clip {
+            if t.advance(&play).is_ok() {
+                tree = Some(t);
+            }
+            println!("Thorn: Exited due to a move being played");
+            continue;
+        }
+        if interrupt {
+            println!("Thorn: Exited due to interrupt request");
+            tree = Some(t);
+            continue;
+        }
+        let stop = if interrupt_return {
+            MonteCarloStop::Return
+        } else if sims >= options.max_sims {
+            MonteCarloStop::SimulationCap
+        } else {
+            MonteCarloStop::Timeout
+        };
+        println!("Thorn: Exited due to {}", stop.name().to_lowercase());
+        println!(
+            "Thorn: Move selected after {} sims ({} reused) and {} seconds.",
+            sims + reused,
+            reused,
+            start_time.elapsed().as_secs_f32()
+        );
+
+        main.send(ThornMessage::Move(
+            t.best(options.policy, options.exploration_factor),
+            stop,
+        ))
+        .unwrap();
+        tree = Some(t);
+    }
+}
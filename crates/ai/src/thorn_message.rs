@@ -0,0 +1,29 @@
+use game::{game::Turn, Move};
+
+use super::{
+    monte_carlo_settings::MonteCarloSettings, monte_carlo_stop::MonteCarloStop, thoughts::Thoughts,
+};
+
+/// Defines the messages that may be passed between the main and Thorn threads
+pub enum ThornMessage {
+    /// Starts position evaluation
+    Start(MonteCarloSettings),
+
+    /// Requests that Thorn return a move
+    Return(),
+
+    /// Requests the information on the current gamestate
+    GetThoughts(Turn),
+
+    /// Returns the information on the current gamestate
+    Thoughts(Thoughts),
+
+    /// Sends a move, with the reason the search stopped
+    Move(Option<Move>, MonteCarloStop),
+
+    /// Calls for the tree to be clipped to the subtree of a move that has been played
+    Clip(Move),
+
+    /// Stops the calculation of a move
+    Interrupt,
+}
@@ -0,0 +1,50 @@
+use game::{game::Turn, Move};
+
+#[derive(Debug, Clone)]
+/// A node in Thorn's search tree
+pub struct ThornNode {
+    /// The move that the node represents
+    pub play: Move,
+    /// The turn for which the node's move is
+    pub turn: Turn,
+    /// The index of the parent node, if any
+    pub parent: Option<usize>,
+    /// The indices of the expanded children
+    pub children: Vec<usize>,
+    /// The legal moves that are yet to be expanded
+    pub unexpanded: Vec<Move>,
+    /// The number of simulations run through the node
+    pub visits: usize,
+    /// The total score of those simulations, relative to the node's turn
+    pub score: f32,
+}
+
+impl ThornNode {
+    /// Constructor function
+    pub fn new(play: Move, turn: Turn, parent: Option<usize>, unexpanded: Vec<Move>) -> ThornNode {
+        ThornNode {
+            play,
+            turn,
+            parent,
+            children: vec![],
+            unexpanded,
+            visits: 0,
+            score: 0.0,
+        }
+    }
+
+    /// Calculates the UCT value for the node
+    pub fn uct(&self, exploration_factor: f32, parent_visits: usize) -> f32 {
+        let visits = self.visits as f32;
+        (self.score / visits) + (exploration_factor * (parent_visits as f32).ln() / visits).sqrt()
+    }
+
+    /// Calculates the score of the node relative to the given turn
+    pub fn score(&self, t: Turn) -> f32 {
+        if self.turn == t {
+            self.score
+        } else {
+            -self.score
+        }
+    }
+}
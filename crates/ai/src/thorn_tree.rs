@@ -0,0 +1,238 @@
+use std::{cmp::Reverse, mem};
+
+use anyhow::Result;
+use fastrand::Rng;
+
+use game::{
+    game::{Game, Turn},
+    value::Value,
+    Move,
+};
+
+use super::{
+    monte_carlo_policy::MonteCarloPolicy, move_thoughts::MoveThoughts, thorn_node::ThornNode,
+    thoughts::Thoughts,
+};
+
+#[derive(Debug)]
+/// Thorn's search tree, stored as a flat list of nodes so that it can be cheaply re-rooted
+pub struct ThornTree {
+    /// The position at the root of the tree
+    pub g: Game,
+    /// The nodes of the tree, with the root at index 0
+    pub nodes: Vec<ThornNode>,
+    /// The random number generator used for tie-breaks, expansion and playouts
+    pub rng: Rng,
+}
+
+impl ThornTree {
+    /// Constructor function
+    pub fn new(g: Game) -> ThornTree {
+        let root = ThornNode::new(vec![], !g.turn, None, Self::unexpanded(&g));
+        ThornTree {
+            g,
+            nodes: vec![root],
+            rng: Rng::new(),
+        }
+    }
+
+    /// Returns the root node
+    pub fn root(&self) -> &ThornNode {
+        &self.nodes[0]
+    }
+
+    /// Returns the moves that may be expanded from a position
+    fn unexpanded(g: &Game) -> Vec<Move> {
+        if g.board.check() == Value::None {
+            g.legal_moves()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Runs a single select, expand, simulate and backpropagate cycle
+    pub fn iterate(&mut self, exploration_factor: f32) {
+        let mut game = self.g.clone();
+        let mut id = 0;
+
+        // Descend through fully expanded nodes by their UCT value
+        while self.nodes[id].unexpanded.is_empty() && !self.nodes[id].children.is_empty() {
+            let parent_visits = self.nodes[id].visits;
+            let mut best_ids = vec![];
+            let mut best_uct = f32::MIN;
+            for &child in &self.nodes[id].children {
+                let uct = self.nodes[child].uct(exploration_factor, parent_visits);
+                if uct > best_uct {
+                    best_ids = vec![child];
+                    best_uct = uct;
+                } else if uct == best_uct {
+                    best_ids.push(child);
+                }
+            }
+            id = self.rng.choice(best_ids).unwrap();
+            game.play(&self.nodes[id].play).unwrap();
+        }
+
+        // Expand a random unexplored move
+        if !self.nodes[id].unexpanded.is_empty() {
+            let unexpanded = &mut self.nodes[id].unexpanded;
+            let play = unexpanded.swap_remove(self.rng.usize(..unexpanded.len()));
+            game.play(&play).unwrap();
+
+            let child = ThornNode::new(play, !game.turn, Some(id), Self::unexpanded(&game));
+            self.nodes.push(child);
+            let child_id = self.nodes.len() - 1;
+            self.nodes[id].children.push(child_id);
+            id = child_id;
+        }
+
+        // Play randomly until the game is over
+        while game.board.check() == Value::None {
+            game.play(self.rng.choice(game.legal_moves().iter()).unwrap())
+                .unwrap();
+        }
+        let result = game.board.check();
+
+        // Apply the result to each node on the path back to the root
+        let mut current = Some(id);
+        while let Some(i) = current {
+            let node = &mut self.nodes[i];
+            node.visits += 1;
+            node.score += if result == node.turn.val() {
+                1.0
+            } else if result == Value::Draw {
+                0.0
+            } else {
+                -1.0
+            };
+            current = node.parent;
+        }
+    }
+
+    /// Returns the move from the root chosen by the policy, or a random legal move if none have been tried
+    pub fn best(&mut self, policy: MonteCarloPolicy, exploration_factor: f32) -> Option<Move> {
+        let root = self.root();
+        let children = root.children.iter().map(|&c| &self.nodes[c]);
+        let chosen = match policy {
+            MonteCarloPolicy::Robust => children.max_by(|a, b| a.visits.cmp(&b.visits)),
+            MonteCarloPolicy::Maximum => children.max_by(|a, b| a.score.total_cmp(&b.score)),
+            MonteCarloPolicy::Frail => children.min_by(|a, b| a.visits.cmp(&b.visits)),
+            MonteCarloPolicy::Minimum => children.min_by(|a, b| a.score.total_cmp(&b.score)),
+            MonteCarloPolicy::Random => None,
+            MonteCarloPolicy::UCB1 => children.max_by(|a, b| {
+                a.uct(exploration_factor, root.visits)
+                    .total_cmp(&b.uct(exploration_factor, root.visits))
+            }),
+        };
+        match chosen {
+            Some(node) => Some(node.play.clone()),
+            None => self.rng.choice(Self::unexpanded(&self.g)),
+        }
+    }
+
+    /// Returns the statistics of the search so far, relative to `t`. Thorn doesn't prove results, so
+    /// none are reported
+    pub fn thoughts(&self, t: Turn) -> Thoughts {
+        let root = self.root();
+        let mut moves: Vec<MoveThoughts> = root
+            .children
+            .iter()
+            .map(|&c| {
+                let node = &self.nodes[c];
+                MoveThoughts {
+                    play: node.play.clone(),
+                    sims: node.visits,
+                    mean: if node.visits > 0 {
+                        node.score(t) / node.visits as f32
+                    } else {
+                        0.0
+                    },
+                    proven: None,
+                    pv: self.principal_variation(c),
+                }
+            })
+            .collect();
+        moves.sort_by_key(|x| Reverse(x.sims));
+
+        Thoughts {
+            sims: root.visits,
+            score: root.score(t),
+            proven: None,
+            moves,
+            started: root.visits,
+            duplicate_leaves: 0,
+        }
+    }
+
+    /// Returns the moves expected to follow a node, taking the most visited child each time
+    fn principal_variation(&self, mut id: usize) -> Vec<Move> {
+        let mut pv = vec![];
+        while let Some(&child) = self.nodes[id]
+            .children
+            .iter()
+            .filter(|&&c| self.nodes[c].visits > 0)
+            .max_by_key(|&&c| self.nodes[c].visits)
+        {
+            pv.push(self.nodes[child].play.clone());
+            id = child;
+        }
+        pv
+    }
+
+    /// Plays a move at the root, keeping the subtree below it and discarding the rest
+    pub fn advance(&mut self, play: &[usize]) -> Result<()> {
+        self.g.play(play)?;
+
+        let child = self
+            .root()
+            .children
+            .iter()
+            .copied()
+            .find(|&c| self.nodes[c].play == play);
+        let Some(child) = child else {
+            *self = ThornTree::new(self.g.clone());
+            return Ok(());
+        };
+
+        // Copy the subtree into a fresh list, breadth first, renumbering as it goes
+        let mut old: Vec<Option<ThornNode>> =
+            mem::take(&mut self.nodes).into_iter().map(Some).collect();
+        let mut queue = vec![(child, None)];
+        let mut i = 0;
+        while i < queue.len() {
+            let (old_id, parent) = queue[i];
+            let mut node = old[old_id].take().unwrap();
+            let new_id = self.nodes.len();
+            node.parent = parent;
+            for c in mem::take(&mut node.children) {
+                queue.push((c, Some(new_id)));
+            }
+            self.nodes.push(node);
+            if let Some(p) = parent {
+                self.nodes[p].children.push(new_id);
+            }
+            i += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Brings the tree to the given position, reusing the current tree if the game has only moved on since
+    pub fn sync(&mut self, g: &Game) {
+        let played = self.g.moves.len();
+        if g.depth != self.g.depth
+            || g.moves.len() < played
+            || g.moves[..played] != self.g.moves[..]
+        {
+            *self = ThornTree::new(g.clone());
+            return;
+        }
+
+        for mv in &g.moves[played..] {
+            if self.advance(&mv[0]).is_err() {
+                *self = ThornTree::new(g.clone());
+                return;
+            }
+        }
+    }
+}
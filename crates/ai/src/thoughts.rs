@@ -0,0 +1,25 @@
+use crate::{monte_carlo_proof::MonteCarloProof, move_thoughts::MoveThoughts};
+
+/// Struct holding the information returned from a `Message::GetThoughts()` message
+#[derive(Debug, Clone)]
+pub struct Thoughts {
+    /// Number of simulations carried out on a move
+    pub sims: usize,
+
+    /// Total score of the simulations, relative to the requested turn
+    pub score: f32,
+
+    /// The proven outcome of the position for the requested turn, in moves from now, if known
+    pub proven: Option<MonteCarloProof>,
+
+    /// The statistics of each move from the position, best first: the most simulated, or for Bramble
+    /// the highest scored
+    pub moves: Vec<MoveThoughts>,
+
+    /// Number of simulations started by the current search, including those still being played out
+    pub started: usize,
+
+    /// Number of those simulations that were sent to a leaf already waiting on another. Zero for
+    /// searches that don't share a tree between threads
+    pub duplicate_leaves: usize,
+}
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+/// The statistics gathered for a position, whichever order of moves it was reached by
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TranspositionEntry {
+    /// The number of simulations through the position
+    pub playouts: f32,
+    /// The score of simulations
+    pub score: f32,
+}
+
+/// A table of statistics shared between every node of the same position, keyed by `Game::hash`
+#[derive(Debug, Default)]
+pub struct TranspositionTable {
+    pub entries: HashMap<u64, TranspositionEntry>,
+}
+
+impl TranspositionTable {
+    /// Constructor function
+    pub fn new() -> TranspositionTable {
+        TranspositionTable {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the statistics of a position, if it has been seen
+    pub fn get(&self, hash: u64) -> Option<&TranspositionEntry> {
+        self.entries.get(&hash)
+    }
+
+    /// Adds to the number of simulations through a position
+    pub fn add_playouts(&mut self, hash: u64, val: f32) {
+        self.entries.entry(hash).or_default().playouts += val;
+    }
+
+    /// Adds to the score of a position
+    pub fn add_score(&mut self, hash: u64, val: f32) {
+        self.entries.entry(hash).or_default().score += val;
+    }
+}
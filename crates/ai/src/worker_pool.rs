@@ -0,0 +1,138 @@
+use std::{
+    sync::mpsc::{sync_channel, TryRecvError},
+    thread,
+};
+
+use id_tree::NodeId;
+
+use game::game::{Game, Turn};
+
+use super::{
+    comms::Comms, noughbert_message::ExplorationRequest, playout::PlayoutResult,
+    playout_policy::PlayoutPolicy, simulation_thread::simulation_thread,
+};
+
+/// The maximum number of leaves sent to a worker at once
+pub const BATCH_SIZE: usize = 16;
+
+/// A simulation thread, and the leaves it has yet to return
+struct Worker {
+    comms: Comms<ExplorationRequest>,
+    pending: Vec<NodeId>,
+}
+
+impl Worker {
+    /// Spawns a new simulation thread
+    fn spawn(n: usize) -> Worker {
+        let (txi, rxi) = sync_channel::<ExplorationRequest>(1);
+        let (txo, rxo) = sync_channel::<ExplorationRequest>(1);
+        thread::Builder::new()
+            .name(format!("Worker {n}"))
+            .spawn(move || simulation_thread(Comms::new(rxo, txi)))
+            .unwrap();
+        Worker {
+            comms: Comms::new(rxi, txo),
+            pending: vec![],
+        }
+    }
+}
+
+/// A fixed set of long-lived simulation threads, fed batches of leaves to play out
+pub struct WorkerPool {
+    workers: Vec<Worker>,
+}
+
+impl WorkerPool {
+    /// Constructor function
+    pub fn new(threads: usize) -> WorkerPool {
+        WorkerPool {
+            workers: (0..threads).map(Worker::spawn).collect(),
+        }
+    }
+
+    /// Grows or shrinks the pool to the given number of threads
+    pub fn resize(&mut self, threads: usize) {
+        while self.workers.len() > threads {
+            let worker = self.workers.pop().unwrap();
+            let _ = worker.comms.send(ExplorationRequest::Stop);
+        }
+        while self.workers.len() < threads {
+            self.workers.push(Worker::spawn(self.workers.len()));
+        }
+    }
+
+    /// Returns the number of threads in the pool
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Returns whether the pool has no threads
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    /// Returns the indices of the threads without a batch
+    pub fn idle(&self) -> Vec<usize> {
+        (0..self.workers.len())
+            .filter(|&i| self.workers[i].pending.is_empty())
+            .collect()
+    }
+
+    /// Sends a batch of leaves to the given thread
+    pub fn dispatch(
+        &mut self,
+        worker: usize,
+        leaves: Vec<(NodeId, Game)>,
+        opt_for: Turn,
+        playout: PlayoutPolicy,
+        rave: bool,
+        seed: u64,
+    ) {
+        let worker = &mut self.workers[worker];
+        worker.pending = leaves.iter().map(|(id, _)| id.clone()).collect();
+        let _ = worker.comms.send(ExplorationRequest::Batch {
+            leaves,
+            opt_for,
+            playout,
+            rave,
+            seed,
+        });
+    }
+
+    /// Collects the results of finished batches, optionally waiting for every thread to finish.
+    ///
+    /// Leaves lost to a crashed thread are returned with no result, and the thread is replaced
+    pub fn collect(&mut self, wait: bool) -> Vec<(NodeId, Option<PlayoutResult>)> {
+        let mut out = vec![];
+        for n in 0..self.workers.len() {
+            if self.workers[n].pending.is_empty() {
+                continue;
+            }
+
+            let message = if wait {
+                self.workers[n]
+                    .comms
+                    .recv()
+                    .map_err(|_| TryRecvError::Disconnected)
+            } else {
+                self.workers[n].comms.try_recv()
+            };
+
+            match message {
+                Ok(ExplorationRequest::Return { results }) => {
+                    self.workers[n].pending.clear();
+                    out.extend(results.into_iter().map(|(id, v)| (id, Some(v))));
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => {
+                    eprintln!("Worker {n} crashed");
+                    let lost = std::mem::take(&mut self.workers[n].pending);
+                    out.extend(lost.into_iter().map(|id| (id, None)));
+                    self.workers[n] = Worker::spawn(n);
+                }
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,96 @@
+//! Checks that the engines can analyse a position endlessly, reporting the statistics of each move.
+
+use std::{sync::mpsc, thread, time::Duration};
+
+use ai::{
+    book_mode::BookMode, bramble::bramble, comms::Comms,
+    monte_carlo_parallelism::MonteCarloParallelism, monte_carlo_policy::MonteCarloPolicy,
+    monte_carlo_settings::MonteCarloSettings, monte_carlo_stop::MonteCarloStop,
+    noughbert_message::NoughbertMessage, playout_policy::PlayoutPolicy, thorn::thorn,
+    thorn_message::ThornMessage, thoughts::Thoughts,
+};
+use game::game::Game;
+
+/// How long each engine is left to analyse before its thoughts are asked for
+const ANALYSIS_TIME: Duration = Duration::from_millis(500);
+
+/// Returns the settings for an endless search of `g`, with limits that would otherwise end it at once
+fn settings(g: &Game) -> MonteCarloSettings {
+    MonteCarloSettings {
+        game: g.clone(),
+        timeout: Duration::ZERO,
+        max_sims: 1,
+        threads: 1,
+        exploration_factor: 0.7,
+        opt_for: g.turn,
+        carry_forward: false,
+        policy: MonteCarloPolicy::Robust,
+        parallelism: MonteCarloParallelism::Tree,
+        transpositions: false,
+        playout: PlayoutPolicy::Random,
+        rave: None,
+        endless: true,
+        seed: Some(0),
+        book: BookMode::Off,
+        perfect: false,
+    }
+}
+
+/// Runs an engine on a new thread, returning the main thread's end of its channel
+fn spawn<T: Send + 'static>(engine: fn(Comms<T>)) -> Comms<T> {
+    let (tx_0, rx_0) = mpsc::sync_channel(0);
+    let (tx_1, rx_1) = mpsc::sync_channel(1);
+    thread::spawn(move || engine(Comms::new(rx_0, tx_1)));
+    Comms::new(rx_1, tx_0)
+}
+
+/// Asserts that the thoughts cover more than the limits allow, with every move's statistics
+fn assert_analysed(t: &Thoughts, g: &Game) {
+    assert!(t.sims > 1);
+    assert_eq!(t.moves.len(), g.legal_moves().len());
+}
+
+#[test]
+fn thorn_analyses_until_asked_for_a_move() {
+    let g = Game::new_depth(2, 0);
+    let main = spawn(thorn);
+    main.send(ThornMessage::Start(settings(&g))).unwrap();
+
+    thread::sleep(ANALYSIS_TIME);
+    main.send(ThornMessage::GetThoughts(g.turn)).unwrap();
+    let ThornMessage::Thoughts(t) = main.recv().unwrap() else {
+        panic!("Thorn didn't answer with its thoughts");
+    };
+    assert_analysed(&t, &g);
+    assert!(t.moves.windows(2).all(|x| x[0].sims >= x[1].sims));
+    assert_eq!(t.moves.iter().map(|x| x.sims).sum::<usize>(), t.sims);
+
+    main.send(ThornMessage::Return()).unwrap();
+    let ThornMessage::Move(Some(mv), stop) = main.recv().unwrap() else {
+        panic!("Thorn didn't return a move");
+    };
+    assert_eq!(stop, MonteCarloStop::Return);
+    assert!(g.legal_moves().contains(&mv));
+}
+
+#[test]
+fn bramble_analyses_until_asked_for_a_move() {
+    let g = Game::new_depth(2, 0);
+    let main = spawn(bramble);
+    main.send(NoughbertMessage::Start(settings(&g))).unwrap();
+
+    thread::sleep(ANALYSIS_TIME);
+    main.send(NoughbertMessage::GetThoughts(g.turn)).unwrap();
+    let NoughbertMessage::Thoughts(t) = main.recv().unwrap() else {
+        panic!("Bramble didn't answer with its thoughts");
+    };
+    assert_analysed(&t, &g);
+    assert!(t.moves.windows(2).all(|x| x[0].mean >= x[1].mean));
+
+    main.send(NoughbertMessage::Return()).unwrap();
+    let NoughbertMessage::Move(Some(mv), stop) = main.recv().unwrap() else {
+        panic!("Bramble didn't return a move");
+    };
+    assert_eq!(stop, MonteCarloStop::Return);
+    assert!(g.legal_moves().contains(&mv));
+}
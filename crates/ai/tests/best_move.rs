@@ -0,0 +1,86 @@
+//! Checks that each policy picks the right move from the searched children of the root.
+
+use ai::{monte_carlo::MonteCarloManager, monte_carlo_policy::MonteCarloPolicy};
+use game::{game::Game, Move};
+
+/// The exploration factor passed to `best`, which it doesn't use
+const EXPLORATION_FACTOR: f32 = 0.7;
+
+/// Returns a manager for an empty tic-tac-toe board with every move from the root expanded, giving
+/// the move at index `i` the score `score(i)` over one playout
+fn expanded_root(score: fn(usize) -> f32) -> MonteCarloManager {
+    let g = Game::new_depth(1, 0);
+    let mut m = MonteCarloManager::new(g.clone(), g.turn);
+    let root = m.tree.root_node_id().unwrap().clone();
+    for _ in 0..g.legal_moves().len() {
+        m.expand(&root);
+    }
+    let children: Vec<_> = m.tree.children_ids(&root).unwrap().cloned().collect();
+    for (i, child) in children.into_iter().enumerate() {
+        let data = m.tree.get_mut(&child).unwrap().data_mut();
+        data.playouts = 1.0;
+        data.score = score(i);
+    }
+    m
+}
+
+/// Returns the move at index `i` among the root's children
+fn child_move(m: &MonteCarloManager, i: usize) -> Move {
+    let root = m.tree.root_node_id().unwrap();
+    m.tree
+        .children(root)
+        .unwrap()
+        .nth(i)
+        .unwrap()
+        .data()
+        .play
+        .clone()
+}
+
+#[test]
+fn maximum_picks_the_best_of_negative_scores() {
+    // The move at index 4 scores highest, though every score is negative
+    let mut m = expanded_root(|i| -1.0 - (i as f32 - 4.0).abs());
+    let best = child_move(&m, 4);
+    let turn = m.g.turn;
+    for _ in 0..10 {
+        assert_eq!(
+            m.best(MonteCarloPolicy::Maximum, turn, EXPLORATION_FACTOR),
+            Some(best.clone())
+        );
+    }
+}
+
+#[test]
+fn minimum_picks_the_worst_of_positive_scores() {
+    // The move at index 4 scores lowest, though every score is positive
+    let mut m = expanded_root(|i| 1.0 + (i as f32 - 4.0).abs());
+    let worst = child_move(&m, 4);
+    let turn = m.g.turn;
+    for _ in 0..10 {
+        assert_eq!(
+            m.best(MonteCarloPolicy::Minimum, turn, EXPLORATION_FACTOR),
+            Some(worst.clone())
+        );
+    }
+}
+
+#[test]
+fn ucb1_picks_the_best_mean_of_negative_scores() {
+    // The move at index 4 has the highest mean, though every mean is negative
+    let mut m = expanded_root(|i| -1.0 - (i as f32 - 4.0).abs());
+    let best = child_move(&m, 4);
+    // A child without playouts has no mean, so must not be picked
+    let root = m.tree.root_node_id().unwrap().clone();
+    let first = m.tree.children_ids(&root).unwrap().next().unwrap().clone();
+    let data = m.tree.get_mut(&first).unwrap().data_mut();
+    data.playouts = 0.0;
+    data.score = 0.0;
+    let turn = m.g.turn;
+    for _ in 0..10 {
+        assert_eq!(
+            m.best(MonteCarloPolicy::UCB1, turn, EXPLORATION_FACTOR),
+            Some(best.clone())
+        );
+    }
+}
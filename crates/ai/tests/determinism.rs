@@ -0,0 +1,156 @@
+//! Checks that a seeded, single-threaded search always makes the same choices.
+
+use std::{sync::mpsc, thread, time::Duration};
+
+use ai::{
+    book_mode::BookMode,
+    comms::Comms,
+    monte_carlo::MonteCarloManager,
+    monte_carlo_parallelism::MonteCarloParallelism,
+    monte_carlo_policy::MonteCarloPolicy,
+    monte_carlo_settings::MonteCarloSettings,
+    noughbert::noughbert,
+    noughbert_message::NoughbertMessage,
+    playout::playout,
+    playout_policy::{PlayoutPolicy, PlayoutWeights},
+    thorn::thorn,
+    thorn_message::ThornMessage,
+};
+use fastrand::Rng;
+use game::{
+    game::{Game, Turn},
+    Move,
+};
+
+/// The number of simulations for each search
+const SIMS: usize = 2000;
+/// The exploration factor used by each search
+const EXPLORATION_FACTOR: f32 = 0.7;
+
+/// Returns the settings for a single-threaded search of `g` with the given seed
+fn settings(g: &Game, seed: u64) -> MonteCarloSettings {
+    MonteCarloSettings {
+        game: g.clone(),
+        // Long enough that only the simulation limit can end the search
+        timeout: Duration::from_secs(600),
+        max_sims: SIMS,
+        threads: 1,
+        exploration_factor: EXPLORATION_FACTOR,
+        opt_for: g.turn,
+        carry_forward: false,
+        policy: MonteCarloPolicy::Robust,
+        parallelism: MonteCarloParallelism::Tree,
+        transpositions: false,
+        playout: PlayoutPolicy::Random,
+        rave: None,
+        endless: false,
+        seed: Some(seed),
+        book: BookMode::Off,
+        perfect: false,
+    }
+}
+
+/// Asks a new Noughbert thread for its move in each position, in turn
+fn noughbert_moves(positions: &[Game], seed: u64) -> Vec<Option<Move>> {
+    let (tx_0, rx_0) = mpsc::sync_channel(0);
+    let (tx_1, rx_1) = mpsc::sync_channel(1);
+    thread::spawn(move || noughbert(Comms::new(rx_0, tx_1)));
+    let main = Comms::new(rx_1, tx_0);
+
+    positions
+        .iter()
+        .map(|g| {
+            main.send(NoughbertMessage::Start(settings(g, seed)))
+                .unwrap();
+            loop {
+                if let NoughbertMessage::Move(mv, _) = main.recv().unwrap() {
+                    break mv;
+                }
+            }
+        })
+        .collect()
+}
+
+/// Asks a new Thorn thread for its move in each position, in turn
+fn thorn_moves(positions: &[Game], seed: u64) -> Vec<Option<Move>> {
+    let (tx_0, rx_0) = mpsc::sync_channel(0);
+    let (tx_1, rx_1) = mpsc::sync_channel(1);
+    thread::spawn(move || thorn(Comms::new(rx_0, tx_1)));
+    let main = Comms::new(rx_1, tx_0);
+
+    positions
+        .iter()
+        .map(|g| {
+            main.send(ThornMessage::Start(settings(g, seed))).unwrap();
+            loop {
+                if let ThornMessage::Move(mv, _) = main.recv().unwrap() {
+                    break mv;
+                }
+            }
+        })
+        .collect()
+}
+
+/// Returns a few positions from a game played with seeded random moves
+fn positions(depth: usize, seed: u64) -> Vec<Game> {
+    let mut rng = Rng::with_seed(seed);
+    let mut g = Game::new_depth(depth, 0);
+    let mut out = vec![g.clone()];
+    for _ in 0..6 {
+        let mv = rng.choice(g.legal_moves()).unwrap();
+        g.play(&mv).unwrap();
+        out.push(g.clone());
+    }
+    out
+}
+
+/// Searches `g` on a manager seeded with `seed`, returning the playouts of each move from the root
+fn root_playouts(g: &Game, seed: u64) -> Vec<(Move, f32)> {
+    let mut m = MonteCarloManager::new(g.clone(), g.turn);
+    m.rng = Rng::with_seed(seed);
+    m.search(SIMS, EXPLORATION_FACTOR, g.turn);
+    m.tree
+        .children(m.tree.root_node_id().unwrap())
+        .unwrap()
+        .map(|x| (x.data().play.clone(), x.data().playouts))
+        .collect()
+}
+
+#[test]
+fn seeded_playouts_repeat() {
+    let g = positions(2, 1).pop().unwrap();
+    let policies = [
+        PlayoutPolicy::Random,
+        PlayoutPolicy::Heavy(PlayoutWeights::default()),
+    ];
+    for policy in policies {
+        for seed in 0..20 {
+            let a = playout(&g, Turn::Player1, policy, true, &mut Rng::with_seed(seed));
+            let b = playout(&g, Turn::Player1, policy, true, &mut Rng::with_seed(seed));
+            assert_eq!(a, b);
+        }
+    }
+}
+
+#[test]
+fn seeded_search_repeats() {
+    for g in positions(2, 2) {
+        assert_eq!(root_playouts(&g, 3), root_playouts(&g, 3));
+    }
+}
+
+#[test]
+fn seeded_noughbert_repeats() {
+    let positions = positions(2, 4);
+    let first = noughbert_moves(&positions, 5);
+    assert!(first.iter().all(|x| x.is_some()));
+    assert_eq!(first, noughbert_moves(&positions, 5));
+}
+
+#[test]
+fn seeded_thorn_repeats() {
+    let positions = positions(2, 6);
+    let first = thorn_moves(&positions, 7);
+    assert!(first.iter().all(|x| x.is_some()));
+    assert_eq!(first, thorn_moves(&positions, 7));
+}
@@ -0,0 +1,159 @@
+//! Checks the exhaustive solver on known positions, and the MCTS-Solver's proofs against it.
+
+use std::{mem::discriminant, sync::mpsc, time::Instant};
+
+use ai::{
+    comms::Comms,
+    monte_carlo::MonteCarloManager,
+    monte_carlo_proof::MonteCarloProof,
+    noughbert_message::NoughbertMessage,
+    solver::{Solver, SOLVER_MAX_OPEN_CELLS},
+    solver_stop::SolverStop,
+};
+use fastrand::Rng;
+use game::{game::Game, value::Value};
+
+/// The most simulations the MCTS-Solver is given to prove a position
+const MAX_SIMS: usize = 20000;
+/// The exploration factor used by the MCTS-Solver
+const EXPLORATION_FACTOR: f32 = 0.7;
+
+/// Plays seeded random moves until there are at most `empty` cells left, starting again if the game
+/// ends first
+fn random_position(depth: usize, empty: usize, seed: u64) -> Game {
+    let mut rng = Rng::with_seed(seed);
+    let mut g = Game::new_depth(depth, 0);
+    loop {
+        if g.board.check() != Value::None {
+            g = Game::new_depth(depth, 0);
+        } else if g.board.moves(&[]).len() - g.moves.len() <= empty {
+            return g;
+        }
+        let mv = rng.choice(g.legal_moves()).unwrap();
+        g.play(&mv).unwrap();
+    }
+}
+
+/// Searches the position until the MCTS-Solver proves it, returning the search
+fn prove(g: &Game, seed: u64) -> MonteCarloManager {
+    let mut m = MonteCarloManager::new(g.clone(), g.turn);
+    m.rng = Rng::with_seed(seed);
+    m.search(MAX_SIMS, EXPLORATION_FACTOR, g.turn);
+    m
+}
+
+/// Asserts that every move the MCTS-Solver has proven has the outcome the solver gives it
+fn assert_proofs_agree(g: &Game, seed: u64) {
+    let solved = Solver::new(None).solve_moves(g).unwrap();
+    let m = prove(g, seed);
+    let root = m.tree.root_node_id().unwrap();
+    for child in m.tree.children(root).unwrap() {
+        let Some(proof) = child.data().proof else {
+            continue;
+        };
+        let (_, outcome) = solved
+            .iter()
+            .find(|(play, _)| play == &child.data().play)
+            .unwrap();
+        // The MCTS-Solver doesn't always find the quickest result, so only the outcomes are compared
+        assert_eq!(
+            discriminant(&proof),
+            discriminant(outcome),
+            "{:?} was proven {proof:?} but solved {outcome:?}",
+            child.data().play
+        );
+    }
+}
+
+#[test]
+fn tic_tac_toe_is_a_draw() {
+    let g = Game::new_depth(1, 0);
+    let mut solver = Solver::new(None);
+    assert_eq!(solver.solve(&g), Some(MonteCarloProof::Draw));
+    for (_, outcome) in solver.solve_moves(&g).unwrap() {
+        assert_eq!(outcome, MonteCarloProof::Draw);
+    }
+}
+
+#[test]
+fn takes_the_quickest_win() {
+    // X has two in the top row, and O two in the middle row
+    let mut g = Game::new_depth(1, 0);
+    for play in [[0], [3], [1], [4]] {
+        g.play(&play).unwrap();
+    }
+    let mut solver = Solver::new(None);
+    let moves = solver.solve_moves(&g).unwrap();
+    assert!(moves.contains(&(vec![2], MonteCarloProof::Win(0))));
+    // Any other move lets O win straight away
+    for (play, outcome) in moves {
+        if play != vec![2] && play != vec![5] {
+            assert_eq!(outcome, MonteCarloProof::Loss(1));
+        }
+    }
+    assert_eq!(solver.best(&g), Some(vec![2]));
+    assert_eq!(solver.solve(&g), Some(MonteCarloProof::Win(1)));
+}
+
+#[test]
+fn gives_up_past_the_limit() {
+    let g = Game::new_depth(2, 0);
+    let mut solver = Solver::new(Some(1000));
+    assert_eq!(solver.solve(&g), None);
+    assert_eq!(solver.best(&g), None);
+    assert_eq!(solver.stop, Some(SolverStop::Budget));
+}
+
+#[test]
+fn gives_up_past_the_deadline() {
+    let g = Game::new_depth(2, 0);
+    let mut solver = Solver::new(None);
+    solver.deadline = Some(Instant::now());
+    assert_eq!(solver.solve(&g), None);
+    assert_eq!(solver.stop, Some(SolverStop::Budget));
+}
+
+#[test]
+fn stops_when_interrupted() {
+    let (tx_0, rx_0) = mpsc::sync_channel(1);
+    let (tx_1, _rx_1) = mpsc::sync_channel(1);
+    let main = Comms::new(rx_0, tx_1);
+    tx_0.send(NoughbertMessage::Interrupt).unwrap();
+
+    let g = Game::new_depth(2, 0);
+    let mut solver = Solver::new(None);
+    solver.main = Some(&main);
+    assert_eq!(solver.best(&g), None);
+    assert_eq!(solver.stop, Some(SolverStop::Interrupt));
+}
+
+#[test]
+fn only_small_positions_are_tried() {
+    assert!(Solver::can_solve(&Game::new_depth(1, 0)));
+    assert!(!Solver::can_solve(&Game::new_depth(2, 0)));
+    assert!(Solver::can_solve(&random_position(
+        2,
+        SOLVER_MAX_OPEN_CELLS,
+        0
+    )));
+}
+
+#[test]
+fn mcts_solver_agrees_on_tic_tac_toe() {
+    for seed in 0..20 {
+        let g = random_position(1, 6, seed);
+        assert_proofs_agree(&g, seed);
+        // Small enough positions are proven outright, matching the solver
+        let proof = prove(&g, seed).root_proof().unwrap();
+        let outcome = Solver::new(None).solve(&g).unwrap().flip();
+        assert_eq!(discriminant(&proof), discriminant(&outcome));
+    }
+}
+
+#[test]
+fn mcts_solver_agrees_on_depth_2_endgames() {
+    for seed in 0..10 {
+        let g = random_position(2, 18, seed);
+        assert_proofs_agree(&g, seed);
+    }
+}
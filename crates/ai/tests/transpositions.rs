@@ -0,0 +1,74 @@
+//! Checks that the transposition table shares statistics between move orders reaching the same position.
+
+use ai::monte_carlo::MonteCarloManager;
+use fastrand::Rng;
+use game::{game::Game, Move};
+use id_tree::NodeId;
+
+/// Expands `parent` until it has a child for `play`, returning that child
+fn child(m: &mut MonteCarloManager, parent: &NodeId, play: &[usize]) -> NodeId {
+    loop {
+        let found = m
+            .tree
+            .children_ids(parent)
+            .unwrap()
+            .find(|x| m.tree.get(x).unwrap().data().play == play)
+            .cloned();
+        if let Some(id) = found {
+            return id;
+        }
+        m.expand(parent);
+    }
+}
+
+/// Adds the nodes for each move of `plays` in turn, returning the last
+fn path(m: &mut MonteCarloManager, plays: &[Move]) -> NodeId {
+    let mut node = m.tree.root_node_id().unwrap().clone();
+    for play in plays {
+        node = child(m, &node, play);
+    }
+    node
+}
+
+#[test]
+fn move_orders_share_statistics() {
+    let g = Game::new_depth(2, 0);
+    let mut m = MonteCarloManager::new(g.clone(), g.turn);
+    m.rng = Rng::with_seed(0);
+    m.set_transpositions(true);
+
+    // X and O each play the same two moves, in either order
+    let a = path(&mut m, &[vec![4, 0], vec![0, 4], vec![4, 1], vec![1, 4]]);
+    let b = path(&mut m, &[vec![4, 1], vec![1, 4], vec![4, 0], vec![0, 4]]);
+    assert_ne!(a, b);
+    let hash = m.tree.get(&a).unwrap().data().hash;
+    assert_eq!(hash, m.tree.get(&b).unwrap().data().hash);
+
+    m.backpropogate_playouts(&a, 1.0);
+    m.backpropogate_value(&a, 1.0);
+    m.backpropogate_playouts(&b, 1.0);
+    m.backpropogate_value(&b, -0.5);
+
+    // Each node only sees its own simulation, but the table holds both
+    assert_eq!(m.tree.get(&a).unwrap().data().playouts, 1.0);
+    assert_eq!(m.tree.get(&b).unwrap().data().playouts, 1.0);
+    let entry = m.table.as_ref().unwrap().get(hash).unwrap();
+    assert_eq!(entry.playouts, 2.0);
+    assert_eq!(entry.score, 0.5);
+}
+
+#[test]
+fn turning_on_fills_the_table_from_the_tree() {
+    let g = Game::new_depth(2, 0);
+    let mut m = MonteCarloManager::new(g.clone(), g.turn);
+    m.rng = Rng::with_seed(0);
+
+    let a = path(&mut m, &[vec![4, 0], vec![0, 4], vec![4, 1], vec![1, 4]]);
+    let b = path(&mut m, &[vec![4, 1], vec![1, 4], vec![4, 0], vec![0, 4]]);
+    m.backpropogate_playouts(&a, 1.0);
+    m.backpropogate_playouts(&b, 1.0);
+
+    m.set_transpositions(true);
+    let hash = m.tree.get(&a).unwrap().data().hash;
+    assert_eq!(m.table.as_ref().unwrap().get(hash).unwrap().playouts, 2.0);
+}
@@ -0,0 +1,305 @@
+//! A fixed-size representation of a game, for playing moves quickly.
+//!
+//! Every board in the game is stored as a set of 9-bit masks, one each for the cells (or completed
+//! sub-boards) held by either player and for drawn sub-boards. Boards are numbered in level order,
+//! so that the top board is `0` and the children of board `b` are `9 * b + 1` to `9 * b + 9`. The
+//! result of every board is cached when it is completed, so nothing is ever recomputed, and moves
+//! are generated into a caller-provided buffer without allocating.
+
+use anyhow::{bail, Result};
+
+use crate::{
+    board::Board,
+    cell::Cell,
+    game::{Game, Turn},
+    value::Value,
+    Move,
+};
+
+/// The deepest game that can be held in a `Bitboard`
+pub const MAX_BITBOARD_DEPTH: usize = 3;
+/// The number of boards in a game of the deepest depth
+const MAX_BOARDS: usize = 91;
+/// The most legal moves that a position can have
+pub const MAX_MOVES: usize = 729;
+/// A mask of all nine cells of a board
+const FULL: u16 = 0x1ff;
+/// The masks of each line of three
+const LINES: [u16; 8] = [
+    0b000_000_111,
+    0b000_111_000,
+    0b111_000_000,
+    0b001_001_001,
+    0b010_010_010,
+    0b100_100_100,
+    0b100_010_001,
+    0b001_010_100,
+];
+
+/// A move in a `Bitboard`, given as the board at the bottom level and the cell within it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BitMove {
+    pub board: u8,
+    pub cell: u8,
+}
+
+/// A game held as bitmasks, for depths of up to `MAX_BITBOARD_DEPTH`
+#[derive(Debug, Clone)]
+pub struct Bitboard {
+    /// The depth of the game
+    pub depth: usize,
+    /// The current turn
+    pub turn: Turn,
+    /// The board that must be played in
+    legal: usize,
+    /// The cells (or sub-boards) of each board won by the first player
+    player_1: [u16; MAX_BOARDS],
+    /// The cells (or sub-boards) of each board won by the second player
+    player_2: [u16; MAX_BOARDS],
+    /// The sub-boards of each board that were drawn
+    drawn: [u16; MAX_BOARDS],
+    /// The cached result of each board
+    results: [Value; MAX_BOARDS],
+}
+
+impl Bitboard {
+    /// Creates an empty board of the given depth
+    pub fn new_depth(depth: usize) -> Result<Bitboard> {
+        if depth == 0 || depth > MAX_BITBOARD_DEPTH {
+            bail!("Bitboards only support depths 1 to {MAX_BITBOARD_DEPTH}")
+        }
+        Ok(Bitboard {
+            depth,
+            turn: Turn::Player1,
+            legal: 0,
+            player_1: [0; MAX_BOARDS],
+            player_2: [0; MAX_BOARDS],
+            drawn: [0; MAX_BOARDS],
+            results: [Value::None; MAX_BOARDS],
+        })
+    }
+
+    /// Converts a `Game` into a bitboard
+    pub fn from_game(g: &Game) -> Result<Bitboard> {
+        let mut b = Bitboard::new_depth(g.depth)?;
+        b.turn = g.turn;
+        b.legal = b.index(&g.legal);
+        b.load(&g.board, 0);
+        Ok(b)
+    }
+
+    /// Converts the bitboard back into a `Game`, which will have no move history
+    pub fn to_game(&self, players: usize) -> Game {
+        let mut g = Game::new_depth(self.depth, players);
+        g.board = self.board(0);
+        g.turn = self.turn;
+        g.legal = self.path(self.legal);
+        g.hash = g.compute_hash();
+        g
+    }
+
+    /// Returns the result of the whole game
+    pub fn check(&self) -> Value {
+        self.results[0]
+    }
+
+    /// Writes the legal moves into `moves`, returning how many there are
+    pub fn legal_moves(&self, moves: &mut [BitMove; MAX_MOVES]) -> usize {
+        if self.check() != Value::None {
+            return 0;
+        }
+        self.legal_moves_in(self.legal, moves, 0)
+    }
+
+    /// Plays a move, which must be legal
+    pub fn play(&mut self, mv: BitMove) {
+        let mut board = mv.board as usize;
+        let mut bit = 1 << mv.cell;
+
+        // Claim the cell, then carry any completed boards upwards
+        loop {
+            let result = self.claim(board, bit);
+            if result == Value::None {
+                break;
+            }
+            self.results[board] = result;
+            if board == 0 {
+                break;
+            }
+            bit = 1 << ((board - 1) % 9);
+            board = (board - 1) / 9;
+            if result == Value::Draw {
+                self.drawn[board] |= bit;
+                bit = 0;
+            }
+        }
+
+        self.legal = self.legal_after(mv.board as usize, mv.cell as usize, self.depth);
+        self.turn = !self.turn;
+    }
+
+    /// Returns whether a move would complete a line of its sub-board for `turn`
+    pub fn wins_board(&self, mv: BitMove, turn: Turn) -> bool {
+        let mask = match turn {
+            Turn::Player1 => self.player_1[mv.board as usize],
+            Turn::Player2 => self.player_2[mv.board as usize],
+        } | (1 << mv.cell);
+        LINES.iter().any(|l| mask & l == *l)
+    }
+
+    /// Returns whether the player to move may choose between several bottom-level boards
+    pub fn free_choice(&self) -> bool {
+        self.check() == Value::None && self.level(self.legal) < self.depth - 1
+    }
+
+    /// Converts a bitboard move into a `Move`
+    pub fn to_move(&self, mv: BitMove) -> Move {
+        let mut pos = self.path(mv.board as usize);
+        pos.push(mv.cell as usize);
+        pos
+    }
+
+    /// Returns the index of a move's cell when counting across the bottom level boards, as by `Move` in base 9
+    pub fn cell_index(&self, mv: BitMove) -> usize {
+        // The bottom level's boards are numbered after the (9^(depth-1) - 1) / 8 boards above them
+        let first = (9usize.pow(self.depth as u32 - 1) - 1) / 8;
+        (mv.board as usize - first) * 9 + mv.cell as usize
+    }
+
+    /// Converts a `Move` into a bitboard move
+    pub fn from_move(&self, pos: &[usize]) -> BitMove {
+        BitMove {
+            board: self.index(&pos[..pos.len() - 1]) as u8,
+            cell: pos[pos.len() - 1] as u8,
+        }
+    }
+
+    /// Sets `bit` on a board for the player to move (unless it is zero), returning the board's result
+    fn claim(&mut self, board: usize, bit: u16) -> Value {
+        let mask = match self.turn {
+            Turn::Player1 => &mut self.player_1[board],
+            Turn::Player2 => &mut self.player_2[board],
+        };
+        *mask |= bit;
+        let mask = *mask;
+
+        // Only the player to move can have just completed a line
+        if bit != 0 && LINES.iter().any(|l| mask & l == *l) {
+            self.turn.val()
+        } else if self.player_1[board] | self.player_2[board] | self.drawn[board] == FULL {
+            Value::Draw
+        } else {
+            Value::None
+        }
+    }
+
+    /// Mirrors `Game::get_legal` for a move in cell `x` of `board`, where `len` is the length of the move
+    fn legal_after(&self, board: usize, x: usize, len: usize) -> usize {
+        if self.results[0] != Value::None || len == 1 {
+            return 0;
+        }
+        // If the move completed its board, carry on as if that board had been played
+        if len >= 3 && self.results[board] != Value::None {
+            return self.legal_after((board - 1) / 9, (board - 1) % 9, len - 1);
+        }
+        let parent = (board - 1) / 9;
+        let target = 9 * parent + 1 + x;
+        if self.results[target] != Value::None {
+            parent
+        } else {
+            target
+        }
+    }
+
+    /// Writes the legal moves within a board into `moves` from `count` onwards, returning the new count
+    fn legal_moves_in(
+        &self,
+        board: usize,
+        moves: &mut [BitMove; MAX_MOVES],
+        mut count: usize,
+    ) -> usize {
+        let empty = !(self.player_1[board] | self.player_2[board] | self.drawn[board]) & FULL;
+        let bottom = self.level(board) == self.depth - 1;
+        for cell in 0..9 {
+            if empty & (1 << cell) == 0 {
+                continue;
+            }
+            if bottom {
+                moves[count] = BitMove {
+                    board: board as u8,
+                    cell: cell as u8,
+                };
+                count += 1;
+            } else {
+                count = self.legal_moves_in(9 * board + 1 + cell, moves, count);
+            }
+        }
+        count
+    }
+
+    /// Returns the level of a board, where the top board is level 0
+    fn level(&self, mut board: usize) -> usize {
+        let mut level = 0;
+        while board != 0 {
+            board = (board - 1) / 9;
+            level += 1;
+        }
+        level
+    }
+
+    /// Returns the index of the board at a position
+    fn index(&self, pos: &[usize]) -> usize {
+        pos.iter().fold(0, |board, x| 9 * board + 1 + x)
+    }
+
+    /// Returns the position of a board
+    fn path(&self, mut board: usize) -> Move {
+        let mut pos = vec![];
+        while board != 0 {
+            pos.insert(0, (board - 1) % 9);
+            board = (board - 1) / 9;
+        }
+        pos
+    }
+
+    /// Fills in a board and its sub-boards from a `Board`
+    fn load(&mut self, b: &Board, board: usize) {
+        for (i, cell) in b.cells.iter().enumerate() {
+            let bit = 1 << i;
+            match cell {
+                Cell::None => {}
+                Cell::Player1 => self.player_1[board] |= bit,
+                Cell::Player2 => self.player_2[board] |= bit,
+                Cell::Board(sub) => {
+                    self.load(sub, 9 * board + 1 + i);
+                    match self.results[9 * board + 1 + i] {
+                        Value::None => {}
+                        Value::Draw => self.drawn[board] |= bit,
+                        Value::Player1 => self.player_1[board] |= bit,
+                        Value::Player2 => self.player_2[board] |= bit,
+                    }
+                }
+            }
+        }
+        self.results[board] = b.check();
+    }
+
+    /// Builds the `Board` for a board and its sub-boards
+    fn board(&self, board: usize) -> Board {
+        let bottom = self.level(board) == self.depth - 1;
+        let cells = (0..9)
+            .map(|i| {
+                if !bottom {
+                    Cell::Board(self.board(9 * board + 1 + i))
+                } else if self.player_1[board] & (1 << i) != 0 {
+                    Cell::Player1
+                } else if self.player_2[board] & (1 << i) != 0 {
+                    Cell::Player2
+                } else {
+                    Cell::None
+                }
+            })
+            .collect();
+        Board::from_cells(cells)
+    }
+}
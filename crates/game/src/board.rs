@@ -0,0 +1,221 @@
+use anyhow::{bail, Ok, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::Move;
+
+use super::{cell::Cell, value::Value};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BoardCells")]
+pub struct Board {
+    pub cells: Vec<Cell>,
+    /// The cached result of the board, kept up to date by `Board::set`
+    #[serde(skip)]
+    value: Value,
+}
+
+/// The saved fields of a `Board`, from which its result is worked out again when loading
+#[derive(Deserialize)]
+struct BoardCells {
+    cells: Vec<Cell>,
+}
+
+impl From<BoardCells> for Board {
+    fn from(fields: BoardCells) -> Self {
+        // Any sub-boards have already been loaded with their own results
+        Board::from_cells(fields.cells)
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Board {
+    /// Creates a new board filled with `Cell::None`
+    pub fn new() -> Self {
+        Board {
+            cells: vec![Cell::None; 9],
+            value: Value::None,
+        }
+    }
+
+    /// Creates a new board with its cells as the input slice
+    pub fn new_cells(cells: [Cell; 9]) -> Self {
+        Self::from_cells(cells.to_vec())
+    }
+
+    /// Creates a new board from a Vec of cells, working out its result
+    pub fn from_cells(cells: Vec<Cell>) -> Self {
+        let mut board = Board {
+            cells,
+            value: Value::None,
+        };
+        board.value = board.evaluate();
+        board
+    }
+
+    /// Recursively creates a new board, containing levels equal to the specified `depth`  
+    pub fn new_depth(depth: usize) -> Self {
+        if depth > 1 {
+            Board {
+                cells: vec![Cell::Board(Board::new_depth(depth - 1)); 9],
+                value: Value::None,
+            }
+        } else {
+            Board::new()
+        }
+    }
+
+    /// Returns the `Cell` at a specified position, taking a slice as input.
+    ///
+    /// The slice should contain the position of the target cell at each level of recursion - I.e.
+    /// `[0]` is the top-left cell of a tic-tac-toe board;
+    /// `[0, 1]` is the upper-middle cell in the top-left board of a depth 2 game
+    pub fn get(&self, pos: &[usize]) -> Option<Cell> {
+        if pos.is_empty() {
+            Some(Cell::Board(self.clone()))
+        } else if pos.len() > 1 {
+            if let Cell::Board(board) = &self.cells[pos[0]] {
+                board.get(&pos[1..])
+            } else {
+                None
+            }
+        } else {
+            Some(self.cells[pos[0]].clone())
+        }
+    }
+
+    // pub fn get_mut(mut self, pos: &[usize]) -> Option<&mut Cell> {
+    //     if pos.is_empty() {
+    //         Some(&mut Cell::Board(self))
+    //     } else if pos.len() > 1 {
+    //         if let Cell::Board(board) = &self.cells[pos[0]] {
+    //             return board.get_mut(&pos[1..]);
+    //         } else {
+    //             None
+    //         }
+    //     } else {
+    //         Some(&mut self.cells[pos[0]])
+    //     }
+    // }
+
+    /// Changes the `Cell` at a given position to the given `Value`
+    pub fn set(&mut self, pos: &[usize], value: Cell) -> Result<()> {
+        if pos.len() > 1 {
+            if let Cell::Board(x) = &mut self.cells[pos[0]] {
+                x.set(&pos[1..], value)?;
+            } else {
+                bail!("No cell at specified depth")
+            }
+        } else {
+            self.cells[pos[0]] = value;
+        }
+        // Only the boards along the path of the change can have a new result
+        self.value = self.evaluate();
+        Ok(())
+    }
+
+    /// Returns whether the board has been won or drawn, as cached by `Board::set`
+    pub fn check(&self) -> Value {
+        self.value
+    }
+
+    /// Recursively recalculates the result of the board and all of its sub-boards, ignoring any cached results
+    pub fn recompute(&self) -> Value {
+        let vals = self
+            .cells
+            .iter()
+            .map(|x| match x {
+                Cell::Board(b) => b.recompute(),
+                x => x.value(),
+            })
+            .collect::<Vec<Value>>();
+        Self::result(&vals)
+    }
+
+    /// Calculates the result of the board from the cached results of its cells
+    fn evaluate(&self) -> Value {
+        let vals = self
+            .cells
+            .iter()
+            .map(super::cell::Cell::value)
+            .collect::<Vec<Value>>();
+        Self::result(&vals)
+    }
+
+    /// Returns the result of a board whose cells have the given values
+    fn result(vals: &[Value]) -> Value {
+        let sets = [
+            [0, 1, 2],
+            [3, 4, 5],
+            [6, 7, 8],
+            [0, 3, 6],
+            [1, 4, 7],
+            [2, 5, 8],
+            [0, 4, 8],
+            [2, 4, 6],
+        ];
+
+        for set in sets {
+            if vals[set[0]] == vals[set[1]]
+                && vals[set[1]] == vals[set[2]]
+                && [Value::Player1, Value::Player2].contains(&vals[set[0]])
+            {
+                return vals[set[0]];
+            }
+        }
+
+        if !vals.contains(&Value::None) {
+            return Value::Draw;
+        }
+
+        Value::None
+    }
+
+    /// Returns a Vec of all possible moves in the board
+    pub fn moves(&self, pos: &[usize]) -> Vec<Move> {
+        let mut l = vec![];
+        for (i, x) in self.cells.iter().enumerate() {
+            let mut v = Vec::with_capacity(pos.len() + 1);
+            v.extend_from_slice(pos);
+            v.push(i);
+            l.append(&mut x.moves(&v));
+        }
+        l
+    }
+
+    /// Returns a Vec of all possible legal moves in the board
+    pub fn legal_moves(&self, pos: &[usize]) -> Vec<Move> {
+        // Create the output vector
+        let mut l = vec![];
+
+        // Iterate over each cell, enumerated
+        for (i, x) in self.cells.iter().enumerate() {
+            // Create a new vector corresponding to the selected cell
+            let mut v = Vec::with_capacity(pos.len() + 1);
+            v.extend_from_slice(pos);
+            v.push(i);
+            l.append(&mut x.legal_moves(&v));
+        }
+        l
+    }
+
+    pub fn dbg_repr(&self) -> String {
+        let mut out = String::new();
+        for (i, cell) in self.cells.iter().map(super::cell::Cell::value).enumerate() {
+            out += match cell {
+                Value::None => ".",
+                Value::Draw => "=",
+                Value::Player1 => "X",
+                Value::Player2 => "O",
+            };
+            if i % 3 == 2 {
+                out += "\n";
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Move;
+
+use super::{board::Board, value::Value};
+
+/// An enum used to differentiate the states of a cell.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Cell {
+    /// An empty cell
+    None,
+    /// The first player
+    Player1,
+    /// The second player
+    Player2,
+    /// Another board
+    Board(Board),
+}
+
+impl Cell {
+    /// Returns the `Value` corresponding to a cell
+    pub fn value(&self) -> Value {
+        match self {
+            Cell::None => Value::None,
+            Cell::Player1 => Value::Player1,
+            Cell::Player2 => Value::Player2,
+            Cell::Board(b) => b.check(), // If the cell is a `Cell::Board`, return the value of the board instead
+        }
+    }
+
+    /// Returns the possible moves within a cell
+    pub fn moves<'a>(&'a self, pos: &'a [usize]) -> Vec<Move> {
+        match self {
+            Cell::None => vec![pos.to_vec()],
+            Cell::Player1 => vec![pos.to_vec()],
+            Cell::Player2 => vec![pos.to_vec()],
+            Cell::Board(b) => b.moves(pos),
+        }
+    }
+
+    /// Returns the possible legal moves within a cell
+    pub fn legal_moves<'a>(&'a self, pos: &'a [usize]) -> Vec<Move> {
+        match self {
+            Cell::None => vec![pos.to_vec()],
+            Cell::Player1 => vec![],
+            Cell::Player2 => vec![],
+            Cell::Board(b) => {
+                if b.check() != Value::None {
+                    vec![]
+                } else {
+                    b.legal_moves(pos)
+                }
+            }
+        }
+    }
+}
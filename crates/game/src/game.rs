@@ -1,20 +1,16 @@
-use std::ops::{Deref, Not};
+use std::ops::Not;
 
 use anyhow::{bail, Ok, Result};
-use raylib::{
-    camera::Camera2D,
-    drawing::{RaylibDraw, RaylibMode2DExt},
-    math::Rectangle,
-    prelude::Vector2,
-};
-
 use serde::{Deserialize, Serialize};
 
-use crate::{common::Move, styles::{
-    BOARD_CELL_MARGIN, CAMERA_DEFAULT_ZOOM, COLOUR_BOARD_BG, COLOUR_BOARD_BG_GREYED, CROSS, THORN,
-}};
+use crate::Move;
 
-use super::{board::Board, cell::Cell, legal::Legal, player::Player, value::Value};
+use super::{
+    board::Board,
+    cell::Cell,
+    value::Value,
+    zobrist::{cell_key, legal_key, TURN_KEY},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Turn {
@@ -42,12 +38,8 @@ impl Not for Turn {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "GameFields")]
 pub struct Game {
-    /// The rectangle in which the board is rendered to the camera
-    pub rect: Rectangle,
-    #[serde(skip)]
-    /// The camera
-    pub camera: Camera2D,
     /// The top level board
     pub board: Board,
     /// The depth of the game
@@ -60,119 +52,66 @@ pub struct Game {
     pub moves: Vec<Vec<Move>>,
     /// The current set of legal moves
     pub legal: Move,
-    /// Cell renderer for Player 1
-    pub player_1: Player,
-    /// Cell renderer for Player 2
-    pub player_2: Player,
+    /// A hash of the position, kept up to date as moves are played and unplayed
+    #[serde(skip)]
+    pub hash: u64,
+}
+
+/// The fields of a `Game` that are saved, from which the hash is worked out again when loading
+#[derive(Deserialize)]
+struct GameFields {
+    board: Board,
+    depth: usize,
+    turn: Turn,
+    players: usize,
+    moves: Vec<Vec<Move>>,
+    legal: Move,
+}
+
+impl From<GameFields> for Game {
+    fn from(fields: GameFields) -> Self {
+        let mut g = Game {
+            board: fields.board,
+            depth: fields.depth,
+            turn: fields.turn,
+            players: fields.players,
+            moves: fields.moves,
+            legal: fields.legal,
+            hash: 0,
+        };
+        g.hash = g.compute_hash();
+        g
+    }
 }
 
 impl Game {
     /// Constructs a new game
-    pub fn new_depth(rect: Rectangle, depth: usize, players: usize) -> Self {
+    pub fn new_depth(depth: usize, players: usize) -> Self {
         Game {
-            rect,
-            camera: Camera2D {
-                zoom: 1.0,
-                ..Default::default()
-            },
             board: Board::new_depth(depth),
             depth,
             turn: Turn::Player1,
             players,
             moves: [].into(),
             legal: vec![],
-            player_1: CROSS,
-            player_2: THORN,
+            hash: legal_key(&[]),
         }
     }
 
-    /// Updates the positions of each cell
-    pub fn update_positions(&mut self) {
-        let m = self.rect.width * BOARD_CELL_MARGIN;
-
-        let irect = Rectangle {
-            x: self.rect.x + m,
-            y: self.rect.x + m,
-            width: self.rect.width - 2.0 * m,
-            height: self.rect.height - 2.0 * m,
-        };
-
-        self.board.update_positions(irect);
-    }
-
-    /// Centres the game camera
-    pub fn centre_camera(&mut self, rect: Rectangle) {
-        self.camera.target = Vector2 {
-            x: self.rect.x + self.rect.width / 2.0f32,
-            y: self.rect.y + self.rect.height / 2.0f32,
-        };
-        self.camera.zoom = f32::min(
-            rect.width / self.rect.width * CAMERA_DEFAULT_ZOOM,
-            rect.height / self.rect.height * CAMERA_DEFAULT_ZOOM,
-        );
-    }
-
-    /// Draws the game into the rectangle
-    pub fn draw<T: RaylibDraw>(
-        &self,
-        rect: Rectangle,
-        on_screen_rect: &Rectangle,
-        game_rect: &Rectangle,
-        d: &mut T,
-        no_check: bool,
-        alpha: bool,
-        hover: Option<&[usize]>,
-    ) {
-        let m = rect.width * BOARD_CELL_MARGIN;
-
-        let mut c = d.begin_mode2D(self.camera);
-
-        // Draws the background
-        c.draw_rectangle_rec(
-            rect,
-            if self.board.check() != Value::None {
-                match self.board.check() {
-                    Value::None => panic!("How the fuck did you manage that"),
-                    Value::Draw => COLOUR_BOARD_BG_GREYED,
-                    Value::Player1 => self.player_1.get_greyed_colour(),
-                    Value::Player2 => self.player_2.get_greyed_colour(),
-                }
-            } else if self.legal.is_empty() {
-                COLOUR_BOARD_BG
-            } else if self.turn == Turn::Player1 {
-                self.player_1.get_greyed_colour()
-            } else {
-                self.player_2.get_greyed_colour()
-            },
-        );
-
-        let irect = Rectangle {
-            x: rect.x + m,
-            y: rect.x + m,
-            width: rect.width - 2.0 * m,
-            height: rect.height - 2.0 * m,
-        };
-
-        // Draws the background for the board
-        let legal: Legal = if self.board.check() != Value::None || self.moves.is_empty() {
-            Legal::ForceDefaultBg
-        } else {
-            Legal::Pos(&self.legal)
-        };
-
-        // Draws the board
-        self.board.draw(
-            irect,
-            on_screen_rect,
-            &mut c,
-            no_check,
-            alpha,
-            hover,
-            legal,
-            self.turn,
-            &self.player_1,
-            &self.player_2,
-        );
+    /// Calculates the hash of the position from scratch
+    pub fn compute_hash(&self) -> u64 {
+        let mut hash = legal_key(&self.legal);
+        if self.turn == Turn::Player2 {
+            hash ^= TURN_KEY;
+        }
+        for pos in self.board.moves(&[]) {
+            match self.board.get(&pos) {
+                Some(Cell::Player1) => hash ^= cell_key(&pos, Turn::Player1),
+                Some(Cell::Player2) => hash ^= cell_key(&pos, Turn::Player2),
+                _ => {}
+            }
+        }
+        hash
     }
 
     /// Makes a move
@@ -200,6 +139,10 @@ impl Game {
                 [pos.to_vec(), self.legal.clone()].to_vec(),
             );
             self.legal = self.get_legal(pos);
+            self.hash ^= cell_key(pos, self.turn)
+                ^ legal_key(&self.moves.last().unwrap()[1])
+                ^ legal_key(&self.legal)
+                ^ TURN_KEY;
             self.turn = !self.turn;
             Ok(())
         } else if let Cell::Board(_) = &mut self.board.get(pos).unwrap() {
@@ -218,8 +161,9 @@ impl Game {
         let mv = self.moves.pop().unwrap();
         let x = &mv[1];
         let _ = self.board.set(&mv[0], Cell::None);
-        self.legal = x.clone();
         self.turn = !self.turn;
+        self.hash ^= cell_key(&mv[0], self.turn) ^ legal_key(&self.legal) ^ legal_key(x) ^ TURN_KEY;
+        self.legal = x.clone();
         Ok(())
     }
 
@@ -272,9 +216,4 @@ impl Game {
             .unwrap()
             .legal_moves(&self.legal)
     }
-
-    /// Wrapper function
-    pub fn get_cell_from_pixel(&self, point: Vector2, no_check: bool) -> Option<Move> {
-        self.board.get_cell_from_pixel(point, no_check)
-    }
 }
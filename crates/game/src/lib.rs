@@ -0,0 +1,14 @@
+//! The rules of Super Noughts and Crosses, with no rendering attached.
+//!
+//! Boards, cells, legality and move history all live here so that anything (the raylib front end,
+//! the AI, tools and tests) can play the game without opening a window.
+
+pub mod bitboard;
+pub mod board;
+pub mod cell;
+pub mod game;
+pub mod value;
+pub mod zobrist;
+
+/// The position of a cell, given as the index of the cell at each level of recursion
+pub type Move = Vec<usize>;
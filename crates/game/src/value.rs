@@ -0,0 +1,12 @@
+/// An enum used to differentiate the states of a board.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Value {
+    /// The board is still being played
+    None,
+    /// The board is drawn
+    Draw,
+    /// The first player has won
+    Player1,
+    /// The second player has won
+    Player2,
+}
@@ -0,0 +1,38 @@
+//! Zobrist-style keys for hashing a position.
+//!
+//! Every feature of a position (a claimed cell, the legal board and the turn) has its own
+//! pseudo-random key, and a position's hash is the XOR of the keys of its features. This lets
+//! `Game::play` and `Game::unplay` keep the hash up to date by toggling only what changed.
+
+use crate::game::Turn;
+
+/// The key toggled whenever the turn passes to the other player
+pub const TURN_KEY: u64 = 0x9e6c_63d0_676a_9a99;
+
+/// Scrambles an index into a well distributed 64-bit key (SplitMix64)
+fn mix(index: u64) -> u64 {
+    let mut z = index.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Gives each position a distinct index, so that positions at different depths never collide
+fn index(pos: &[usize]) -> u64 {
+    pos.iter()
+        .fold(0u64, |acc, x| acc.wrapping_mul(10).wrapping_add(*x as u64 + 1))
+}
+
+/// Returns the key for the cell at `pos` being claimed by `turn`
+pub fn cell_key(pos: &[usize], turn: Turn) -> u64 {
+    let player = match turn {
+        Turn::Player1 => 1,
+        Turn::Player2 => 2,
+    };
+    mix(index(pos).wrapping_mul(4).wrapping_add(player))
+}
+
+/// Returns the key for `legal` being the board that must be played in
+pub fn legal_key(legal: &[usize]) -> u64 {
+    mix(index(legal).wrapping_mul(4).wrapping_add(3))
+}
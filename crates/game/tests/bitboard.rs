@@ -0,0 +1,81 @@
+//! Checks that a `Bitboard` always agrees with the `Game` it was made from.
+
+mod common;
+
+use common::{check_random_games, Step};
+use game::{
+    bitboard::{BitMove, Bitboard, MAX_BITBOARD_DEPTH, MAX_MOVES},
+    cell::Cell,
+    game::Game,
+    value::Value,
+    Move,
+};
+
+/// The depths played at, with the number of random games at each
+const GAMES: [(usize, u64); 3] = [(1, 100), (2, 100), (3, 20)];
+
+/// Returns the legal moves of the bitboard as `Move`s, sorted
+fn bitboard_moves(b: &Bitboard) -> Vec<Move> {
+    let mut buffer = [BitMove::default(); MAX_MOVES];
+    let count = b.legal_moves(&mut buffer);
+    let mut moves: Vec<Move> = buffer[..count].iter().map(|x| b.to_move(*x)).collect();
+    moves.sort();
+    moves
+}
+
+/// Asserts that the bitboard holds the same position as the game
+fn assert_matches(b: &Bitboard, g: &Game) {
+    assert_eq!(b.check(), g.board.check());
+    assert_eq!(b.turn, g.turn);
+
+    let mut moves = if g.board.check() == Value::None {
+        g.legal_moves()
+    } else {
+        vec![]
+    };
+    moves.sort();
+    assert_eq!(bitboard_moves(b), moves);
+    for mv in &moves {
+        assert_eq!(&b.to_move(b.from_move(mv)), mv);
+    }
+
+    let round_trip = b.to_game(g.players);
+    assert_eq!(round_trip.board, g.board);
+    assert_eq!(round_trip.legal, g.legal);
+    assert_eq!(round_trip.hash, g.hash);
+}
+
+#[test]
+fn bitboard_matches_after_every_move() {
+    let mut b = Bitboard::new_depth(1).unwrap();
+    check_random_games(&GAMES, |g, step| {
+        match step {
+            Step::Start => {
+                b = Bitboard::new_depth(g.depth).unwrap();
+                assert_matches(&b, g);
+            }
+            Step::Play(mv) => {
+                let bit = b.from_move(mv);
+                // A move wins its board exactly when the game's board containing it is won by the move
+                let wins = b.wins_board(bit, !g.turn);
+                b.play(bit);
+                let Some(Cell::Board(board)) = g.board.get(&mv[..mv.len() - 1]) else {
+                    panic!("{mv:?} isn't in a board");
+                };
+                assert_eq!(wins, board.check() == (!g.turn).val());
+                assert_matches(&b, g);
+            }
+            // Bitboards can't unplay, so are only checked by converting the game afresh
+            Step::Unplay => {}
+        }
+        // Converting the game afresh gives the same position as playing it move by move
+        assert_matches(&Bitboard::from_game(g).unwrap(), g);
+    });
+}
+
+#[test]
+fn unsupported_depths_are_refused() {
+    assert!(Bitboard::new_depth(0).is_err());
+    assert!(Bitboard::new_depth(MAX_BITBOARD_DEPTH + 1).is_err());
+    assert!(Bitboard::from_game(&Game::new_depth(MAX_BITBOARD_DEPTH + 1, 0)).is_err());
+}
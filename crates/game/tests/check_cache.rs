@@ -0,0 +1,51 @@
+//! Checks that the results cached by `Board::set` always match a full recomputation.
+
+mod common;
+
+use common::{check_random_games, Step};
+use game::{board::Board, cell::Cell, game::Game, value::Value};
+
+/// The depths played at, with the number of random games at each. Depth 4 games are long, and
+/// every move recomputes the whole board, so only one is played
+const GAMES: [(usize, u64); 4] = [(1, 50), (2, 50), (3, 10), (4, 1)];
+
+/// Asserts that every board in `board` has a cached result equal to its recomputed result
+fn assert_cache_matches(board: &Board) {
+    assert_eq!(board.check(), board.recompute());
+    for cell in &board.cells {
+        if let Cell::Board(b) = cell {
+            assert_cache_matches(b);
+        }
+    }
+}
+
+#[test]
+fn cache_matches_after_every_move_and_unplay() {
+    check_random_games(&GAMES, |g, step| {
+        assert_cache_matches(&g.board);
+        if let Step::Unplay = step {
+            if g.moves.is_empty() {
+                assert_eq!(g.board, Board::new_depth(g.depth));
+            }
+        }
+    });
+}
+
+#[test]
+fn cache_is_restored_when_loading() {
+    fastrand::seed(0);
+    let mut g = Game::new_depth(3, 0);
+    for _ in 0..200 {
+        if g.board.check() != Value::None {
+            break;
+        }
+        let mv = fastrand::choice(g.legal_moves()).unwrap();
+        g.play(&mv).unwrap();
+    }
+
+    // The cached results aren't saved, so have to be worked out again from the cells
+    let json = serde_json::to_string(&g).unwrap();
+    let board = serde_json::from_str::<Game>(&json).unwrap().board;
+    assert_cache_matches(&board);
+    assert_eq!(board, g.board);
+}
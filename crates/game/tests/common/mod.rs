@@ -0,0 +1,39 @@
+//! Random games shared by the tests that check a representation against `Game`.
+
+// Not every test needs everything about each step
+#![allow(dead_code)]
+
+use game::{game::Game, value::Value, Move};
+
+/// The point in a random game at which the game is checked
+pub enum Step<'a> {
+    /// The new game, before any move
+    Start,
+    /// Just after the move was played
+    Play(&'a Move),
+    /// Just after a move was unplayed
+    Unplay,
+}
+
+/// Plays `games` random games to the end and back at each depth, seeded from 0 upwards, calling
+/// `check` on the game at the start, after every move and after every unplay
+pub fn check_random_games(games: &[(usize, u64)], mut check: impl FnMut(&Game, Step)) {
+    for &(depth, count) in games {
+        for seed in 0..count {
+            fastrand::seed(seed);
+            let mut g = Game::new_depth(depth, 0);
+            check(&g, Step::Start);
+
+            while g.board.check() == Value::None {
+                let mv = fastrand::choice(g.legal_moves()).unwrap();
+                g.play(&mv).unwrap();
+                check(&g, Step::Play(&mv));
+            }
+
+            while !g.moves.is_empty() {
+                g.unplay().unwrap();
+                check(&g, Step::Unplay);
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Checks that the hash kept up to date by `Game::play` and `Game::unplay` always matches a full recomputation.
+
+mod common;
+
+use common::{check_random_games, Step};
+use game::{game::Game, value::Value};
+
+/// The depths played at, with the number of random games at each
+const GAMES: [(usize, u64); 3] = [(1, 100), (2, 100), (3, 20)];
+
+#[test]
+fn hash_matches_after_every_move_and_unplay() {
+    let mut start = 0;
+    check_random_games(&GAMES, |g, step| {
+        assert_eq!(g.hash, g.compute_hash());
+        match step {
+            Step::Start => start = g.hash,
+            Step::Unplay if g.moves.is_empty() => assert_eq!(g.hash, start),
+            _ => {}
+        }
+    });
+}
+
+#[test]
+fn transpositions_share_a_hash() {
+    // X and O each play the same two moves, in either order
+    let mut a = Game::new_depth(2, 0);
+    for play in [[4, 0], [0, 4], [4, 1], [1, 4]] {
+        a.play(&play).unwrap();
+    }
+    let mut b = Game::new_depth(2, 0);
+    for play in [[4, 1], [1, 4], [4, 0], [0, 4]] {
+        b.play(&play).unwrap();
+    }
+    assert_ne!(a.moves, b.moves);
+    assert_eq!(a.hash, b.hash);
+}
+
+#[test]
+fn hash_is_restored_when_loading() {
+    fastrand::seed(0);
+    let mut g = Game::new_depth(2, 0);
+    for _ in 0..20 {
+        if g.board.check() != Value::None {
+            break;
+        }
+        let mv = fastrand::choice(g.legal_moves()).unwrap();
+        g.play(&mv).unwrap();
+    }
+
+    // The hash isn't saved, so has to be worked out again from the position
+    let json = serde_json::to_string(&g).unwrap();
+    assert!(!json.contains("hash"));
+    let loaded: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.hash, g.hash);
+}
@@ -1,12 +0,0 @@
-pub mod comms;
-pub mod thorn;
-pub mod thorn_message;
-pub mod graphvis;
-pub mod noughbert_message;
-pub mod monte_carlo;
-pub mod monte_carlo_node;
-pub mod monte_carlo_policy;
-pub mod monte_carlo_settings;
-pub mod noughbert;
-pub mod simulation_thread;
-pub mod thoughts;
\ No newline at end of file
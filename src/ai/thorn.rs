@@ -1,5 +0,0 @@
-
-
-pub fn thorn() {
-
-}
@@ -1,22 +0,0 @@
-use crate::{common::Move, game::game::Turn};
-
-use super::{monte_carlo_settings::MonteCarloSettings, thoughts::Thoughts};
-
-pub enum ThornMessage {
-    /// Starts position evaluation
-    Start(MonteCarloSettings),
-
-    /// Requests the information on the current gamestate
-    GetThoughts(Turn),
-
-    /// Returns the information on the current gamestate
-    Thoughts(Thoughts),
-
-    /// Sends a move
-    Move(Option<Move>),
-
-    /// Calls for the tree to be clipped
-
-    /// Stops the calculation of a move
-    Interrupt,
-}
\ No newline at end of file
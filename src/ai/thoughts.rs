@@ -1,9 +0,0 @@
-/// Struct holding the information returned from a `Message::GetThoughts()` message
-#[derive(Debug, Clone, Copy)]
-pub struct Thoughts {
-    /// Number of simulations carried out on a move
-    pub sims: usize,
-
-    /// Number of wins on a move
-    pub score: f32,
-}
\ No newline at end of file
@@ -1,25 +1,32 @@
 use raylib::{
     color::Color,
     drawing::RaylibDraw,
-    math::Rectangle,
+    math::{Rectangle, Vector2},
     text::{Font, RaylibFont},
     RaylibHandle, RaylibThread,
 };
 
+use std::time::Duration;
+
+use ai::{
+    engine::Engine, monte_carlo_parallelism::MonteCarloParallelism,
+    monte_carlo_proof::MonteCarloProof, monte_carlo_settings::MonteCarloSettings,
+    noughbert_message::NoughbertMessage, thorn_message::ThornMessage,
+};
+use game::game::{Game, Turn};
+
 use crate::{
-    game::{
-        game::{Game, Turn},
-        player::Player,
-        symbol::Symbol,
-    },
+    hint::Hint,
+    render::{player::Player, symbol::Symbol, view::View},
+    state::State,
     styles::{
-        BARBEQUE, COLOUR_BOARD_BG_GREYED, COLOUR_CELL_BG_GREYED, COLOUR_DRAW_FG, CROSS,
-        DO_COLOURED_GREYS, FISH, IRELAND, NOUGHT, THORN, UI_PANEL_WIDTH,
+        BARBEQUE, BOARD_CELL_MARGIN, BOARD_LINE_THICK, COLOUR_BOARD_BG_GREYED,
+        COLOUR_CELL_BG_GREYED, COLOUR_DRAW_FG, COLOUR_UI_BG, COLOUR_UI_DIVIDER, CROSS,
+        COMPUTER_PERFECT_STRENGTH, DO_COLOURED_GREYS, FISH, HINT_TIME, IRELAND, NOUGHT, THORN, UI_EVALUATION_BAR_WIDTH, UI_PANEL_WIDTH,
     },
+    ui::ui_state::UIState,
 };
 
-pub type Move = Vec<usize>;
-
 /// Draws a draw (`Value::Draw`) into the given rectangle 'rect' onto `d`.
 pub fn draw_draw<T: RaylibDraw>(rect: Rectangle, d: &mut T) {
     // Calculating the spacing between the lines.
@@ -44,6 +51,55 @@ pub fn draw_draw<T: RaylibDraw>(rect: Rectangle, d: &mut T) {
 
 //----------// Miscellaneous quick procedures //----------//
 
+/// Draws a bar along the right of `rect`, filled from the bottom in the first player's colour as far as
+/// `evaluation` (from -1 to 1) favours them, and from the top in the second player's colour.
+pub fn draw_evaluation_bar<T: RaylibDraw>(
+    d: &mut T,
+    rect: Rectangle,
+    evaluation: f32,
+    player_1: &Player,
+    player_2: &Player,
+) {
+    let bar = Rectangle {
+        x: rect.x + rect.width - 2.0 * UI_EVALUATION_BAR_WIDTH,
+        y: rect.y + UI_EVALUATION_BAR_WIDTH,
+        width: UI_EVALUATION_BAR_WIDTH,
+        height: rect.height - 2.0 * UI_EVALUATION_BAR_WIDTH,
+    };
+    let share = (evaluation.clamp(-1.0, 1.0) + 1.0) / 2.0;
+    let split = bar.height * (1.0 - share);
+
+    d.draw_rectangle_rec(
+        Rectangle {
+            height: split,
+            ..bar
+        },
+        player_2.foreground,
+    );
+    d.draw_rectangle_rec(
+        Rectangle {
+            y: bar.y + split,
+            height: bar.height - split,
+            ..bar
+        },
+        player_1.foreground,
+    );
+    // Mark the middle, so that an even game is easy to see
+    d.draw_line_ex(
+        Vector2 {
+            x: bar.x,
+            y: bar.y + bar.height / 2.0,
+        },
+        Vector2 {
+            x: bar.x + bar.width,
+            y: bar.y + bar.height / 2.0,
+        },
+        2.0,
+        COLOUR_UI_BG,
+    );
+    d.draw_rectangle_lines_ex(bar, 2.0, COLOUR_UI_DIVIDER);
+}
+
 /// Returns the correct colour for a greyed out cell.
 pub fn get_greyed_colour_cell(turn: Turn, player_1: &Player, player_2: &Player) -> Color {
     if DO_COLOURED_GREYS {
@@ -117,6 +173,27 @@ pub fn get_board_rect(depth: usize) -> Rectangle {
     }
 }
 
+/// Returns the rectangles of each cell within a board drawn into `rect`, which are then used for drawing
+pub fn get_cell_rects(rect: Rectangle) -> [Rectangle; 9] {
+    let length = rect.width;
+    let thickness = BOARD_LINE_THICK * length;
+    let margin = BOARD_CELL_MARGIN * length;
+    let column_size = (length - (2.0 * thickness)) / 3.0;
+
+    let mut cell_positions = [Rectangle::EMPTY; 9];
+    for y in 0..3 {
+        for x in 0..3 {
+            cell_positions[3 * y + x] = Rectangle {
+                x: rect.x + x as f32 * (column_size + thickness) + margin,
+                y: rect.y + y as f32 * (column_size + thickness) + margin,
+                width: column_size - 2.0 * margin,
+                height: column_size - 2.0 * margin,
+            }
+        }
+    }
+    cell_positions
+}
+
 /// Function to return the colour that would be seen when layering a foregroudn colour with transparrency over a solid background colour
 pub fn get_rgb_from_rgba(fg: Color, bg: Color) -> Color {
     let alpha = fg.a as f32 / 255.0;
@@ -127,15 +204,30 @@ pub fn get_rgb_from_rgba(fg: Color, bg: Color) -> Color {
     Color { r, g, b, a: 255 }
 }
 
+/// Returns how many of the winner's own moves a proven win takes, when the game ends `plies` moves
+/// from now and `winner_to_move` says whether the winner makes the first of them
+pub fn winner_moves(plies: usize, winner_to_move: bool) -> usize {
+    if winner_to_move {
+        plies.div_ceil(2)
+    } else {
+        plies / 2
+    }
+}
+
 /// Dynamically change the window title based on the current game depth and selected symbols
-pub fn update_window_title(rl: &mut RaylibHandle, rlthread: &mut RaylibThread, g: &Game) {
+pub fn update_window_title(
+    rl: &mut RaylibHandle,
+    rlthread: &mut RaylibThread,
+    g: &Game,
+    view: &View,
+) {
     let mut out = String::new();
 
     for _ in 0..g.depth - 1 {
         out += "Super "
     }
 
-    out += &(g.player_2.symbol.name() + " and " + &g.player_1.symbol.name());
+    out += &(view.player_2.symbol.name() + " and " + &view.player_1.symbol.name());
 
     rl.set_window_title(rlthread, &out)
 }
@@ -151,3 +243,137 @@ pub fn get_player_from_symbol(symbol: &Symbol) -> Player {
         Symbol::Ireland => IRELAND,
     }
 }
+
+/// Queues a move request to the engine chosen for the player whose turn it is
+pub fn request_computer_move(g: &Game, ui_state: &UIState, state: &mut State) {
+    // The engines are needed for the move, so stop any analysis first
+    stop_analysis(state);
+    let settings = computer_settings(g, ui_state);
+    match ui_state.engine(g.turn) {
+        Engine::Noughbert => state
+            .message_queue
+            .insert(state.message_queue.len(), NoughbertMessage::Start(settings)),
+        Engine::Thorn => state
+            .thorn_queue
+            .insert(state.thorn_queue.len(), ThornMessage::Start(settings)),
+        Engine::Bramble => state
+            .bramble_queue
+            .insert(state.bramble_queue.len(), NoughbertMessage::Start(settings)),
+    }
+    state.waiting_for_move = true;
+}
+
+/// Queues an endless search of the current position by `engine`, replacing any analysis already running
+pub fn start_analysis(g: &Game, engine: Engine, ui_state: &UIState, state: &mut State) {
+    stop_analysis(state);
+    let settings = MonteCarloSettings {
+        parallelism: MonteCarloParallelism::Tree,
+        endless: true,
+        ..computer_settings(g, ui_state)
+    };
+    match engine {
+        Engine::Noughbert => state
+            .message_queue
+            .insert(state.message_queue.len(), NoughbertMessage::Start(settings)),
+        Engine::Thorn => state
+            .thorn_queue
+            .insert(state.thorn_queue.len(), ThornMessage::Start(settings)),
+        Engine::Bramble => state
+            .bramble_queue
+            .insert(state.bramble_queue.len(), NoughbertMessage::Start(settings)),
+    }
+    state.analysis_hash = Some(g.hash);
+    state.analysis_engine = engine;
+    state.waiting_for_thoughts = true;
+    state.currrent_thoughts = None;
+}
+
+/// Starts thinking about a hint for the player to move with their chosen engine, which is taken from the
+/// analysis once `HINT_TIME` is up
+pub fn request_hint(g: &Game, ui_state: &UIState, state: &mut State) {
+    // An analysis of this position by the same engine can be carried on with
+    let engine = ui_state.engine(g.turn);
+    if state.analysis_hash != Some(g.hash) || state.analysis_engine != engine {
+        start_analysis(g, engine, ui_state, state);
+    }
+    state.hint = None;
+    state.hint_timer = HINT_TIME;
+    state.hints_used[g.turn as usize] += 1;
+}
+
+/// Returns the best move in the current thoughts as a hint for the player to move, if there are any
+pub fn take_hint(g: &Game, state: &State) -> Option<Hint> {
+    let best = state.currrent_thoughts.as_ref()?.moves.first()?;
+    // The thoughts are relative to the first player
+    let (mean, proven) = if g.turn == Turn::Player1 {
+        (best.mean, best.proven)
+    } else {
+        (-best.mean, best.proven.map(|p| p.flip()))
+    };
+    let expected_score = match proven {
+        Some(MonteCarloProof::Win(_)) => 1.0,
+        Some(MonteCarloProof::Draw) => 0.5,
+        Some(MonteCarloProof::Loss(_)) => 0.0,
+        None => (mean + 1.0) / 2.0,
+    };
+    Some(Hint {
+        hash: g.hash,
+        play: best.play.clone(),
+        expected_score,
+    })
+}
+
+/// Queues an interrupt to the engine analysing, if there is one, stopping the analysis
+pub fn stop_analysis(state: &mut State) {
+    if state.analysis_hash.take().is_some() {
+        match state.analysis_engine {
+            Engine::Noughbert => state
+                .message_queue
+                .insert(state.message_queue.len(), NoughbertMessage::Interrupt),
+            Engine::Thorn => state
+                .thorn_queue
+                .insert(state.thorn_queue.len(), ThornMessage::Interrupt),
+            Engine::Bramble => state
+                .bramble_queue
+                .insert(state.bramble_queue.len(), NoughbertMessage::Interrupt),
+        }
+    }
+}
+
+/// Returns the settings for a search of the current position, as chosen in the UI
+fn computer_settings(g: &Game, ui_state: &UIState) -> MonteCarloSettings {
+    MonteCarloSettings {
+        game: g.clone(),
+        timeout: Duration::from_secs(ui_state.max_time as u64),
+        max_sims: ui_state.max_sims,
+        threads: ui_state.ai_threads,
+        exploration_factor: ui_state.exploration(),
+        opt_for: g.turn,
+        carry_forward: true,
+        policy: ui_state.policy(g.turn),
+        parallelism: ui_state.parallelism,
+        transpositions: ui_state.transpositions,
+        playout: ui_state.playout,
+        rave: None,
+        endless: false,
+        seed: None,
+        book: ui_state.book,
+        perfect: ui_state.ai_strength == COMPUTER_PERFECT_STRENGTH && !ui_state.is_ai_modified,
+    }
+}
+
+/// Queues an interrupt to every engine, stopping any move being calculated
+pub fn interrupt_computers(state: &mut State) {
+    // The analysis is stopped too, so let it be restarted on the new position
+    state.analysis_hash = None;
+    state.evaluated_hash = None;
+    state
+        .message_queue
+        .insert(state.message_queue.len(), NoughbertMessage::Interrupt);
+    state
+        .thorn_queue
+        .insert(state.thorn_queue.len(), ThornMessage::Interrupt);
+    state
+        .bramble_queue
+        .insert(state.bramble_queue.len(), NoughbertMessage::Interrupt);
+}
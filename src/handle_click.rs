@@ -2,14 +2,25 @@ use std::fs;
 
 use raylib::{ffi::MouseButton, math::Vector2, open_url, RaylibHandle, RaylibThread};
 
+use ai::thorn_message::ThornMessage;
+use game::{
+    game::{Game, Turn},
+    value::Value,
+    Move,
+};
+
 use crate::{
-    ai::noughbert_message::NoughbertMessage,
-    common::{get_board_rect, get_player_from_symbol, update_window_title, Move},
-    game::game::{Game, Turn},
+    common::{
+        get_board_rect, get_player_from_symbol, interrupt_computers, request_hint, stop_analysis,
+        update_window_title,
+    },
+    render::view::View,
+    saved_game::SavedGame,
     state::State,
     styles::{
         COMPUTER_LEVEL_1_SIMS, COMPUTER_LEVEL_2_SIMS, COMPUTER_LEVEL_3_SIMS,
-        COMPUTER_RESPONSE_DELAY, COMPUTER_SIM_SCALING, DEFAULT_THOUGHTS_DELAY, RULES_URL,
+        COMPUTER_PERFECT_STRENGTH, COMPUTER_RESPONSE_DELAY, COMPUTER_SIM_SCALING,
+        DEFAULT_THOUGHTS_DELAY, RULES_URL,
     },
     ui::{textbox::Textbox, ui::UI, ui_tab::UITab},
 };
@@ -56,17 +67,21 @@ pub fn handle_click(
                         handle_settings_tab_click(mouse_pos, ui, state, g, rl, rlthread);
                     }
                     UITab::Keybinds => handle_keybinds_tab_click(mouse_pos, ui),
-                    UITab::Symbols => handle_symbols_tab_click(mouse_pos, ui, g, rl, rlthread),
+                    UITab::Symbols => {
+                        handle_symbols_tab_click(mouse_pos, ui, g, state, rl, rlthread)
+                    }
                     UITab::None => {}
                 }
             }
         } else if let Some(ref cell) = *hovered_cell {
             // This means that the mouse click was in the game.
             if g.players == 2 || (g.players == 1 && g.turn == Turn::Player1) {
-                let _ = g.play(cell);
-                state
-                    .message_queue
-                    .insert(state.message_queue.len(), NoughbertMessage::Interrupt);
+                if g.play(cell).is_ok() {
+                    state
+                        .thorn_queue
+                        .insert(state.thorn_queue.len(), ThornMessage::Clip(cell.clone()));
+                }
+                interrupt_computers(state);
                 let x = fastrand::usize(5..20) as f32;
                 state.move_delay = COMPUTER_RESPONSE_DELAY * x / 10.0;
             }
@@ -75,9 +90,35 @@ pub fn handle_click(
 }
 
 fn handle_game_tab_click(ui: &mut UI, mouse_pos: Vector2, g: &mut Game, state: &mut State) {
+    // Toggle analysis if the analysis button is clicked, the main loop starts it when it can
+    if ui
+        .game_elements
+        .analysis_toggle
+        .check_collision_point_rec(mouse_pos)
+    {
+        state.analysing ^= true;
+        if !state.analysing {
+            stop_analysis(state);
+        }
+    }
+
+    // Ask for a hint if the hint button is clicked
+    if ui.game_elements.hint.check_collision_point_rec(mouse_pos)
+        && g.board.check() == Value::None
+        && !state.waiting_for_move
+        && state.hint_timer <= 0.0
+    {
+        request_hint(g, &ui.state, state);
+    }
+
     // Export the game to a file if Export is clicked
     if ui.game_elements.export.check_collision_point_rec(mouse_pos) {
-        let game_serial = serde_json::to_string(g).unwrap();
+        let game_serial = serde_json::to_string(&SavedGame {
+            game: g.clone(),
+            view: state.view.clone(),
+            hints: state.hints_used,
+        })
+        .unwrap();
         let _ = fs::create_dir("./exports");
         let filename = &format!("{:x}", md5::compute(game_serial.clone()))[..16];
         match fs::write(format!("./exports/{filename}.xo"), game_serial) {
@@ -155,21 +196,15 @@ fn handle_settings_tab_click(
         .check_collision_point_rec(offset)
     {
         // Stop any currently calculating moves
-        state
-            .message_queue
-            .insert(state.message_queue.len(), NoughbertMessage::Interrupt);
+        interrupt_computers(state);
         // Stop waiting to receive a move
         state.waiting_for_move = false;
         // Set a new game based on the current UI state
-        *g = Game::new_depth(
-            get_board_rect(ui.state.depth),
-            ui.state.depth,
-            ui.state.players,
-        );
-        // Re-initialise the game
-        g.update_positions();
-        g.centre_camera(state.game_rect);
-        g.camera.offset = Vector2 {
+        *g = Game::new_depth(ui.state.depth, ui.state.players);
+        // Re-initialise the view
+        state.view = View::new(get_board_rect(ui.state.depth));
+        state.view.centre_camera(state.game_rect);
+        state.view.camera.offset = Vector2 {
             x: state.game_rect.width / 2.0f32,
             y: state.game_rect.height,
         };
@@ -178,18 +213,22 @@ fn handle_settings_tab_click(
             let l = match ui.state.ai_strength {
                 1 => COMPUTER_LEVEL_1_SIMS,
                 2 => COMPUTER_LEVEL_2_SIMS,
-                3 => COMPUTER_LEVEL_3_SIMS,
+                3 | COMPUTER_PERFECT_STRENGTH => COMPUTER_LEVEL_3_SIMS,
                 _ => 0,
             };
             ui.state.max_sims = l * (COMPUTER_SIM_SCALING.pow((g.depth - 1).try_into().unwrap()));
         }
         // Update the symbols and window title
-        g.player_1 = get_player_from_symbol(&ui.state.player_1);
-        g.player_2 = get_player_from_symbol(&ui.state.player_2);
-        update_window_title(rl, rlthread, g);
+        state.view.player_1 = get_player_from_symbol(&ui.state.player_1);
+        state.view.player_2 = get_player_from_symbol(&ui.state.player_2);
+        update_window_title(rl, rlthread, g, &state.view);
 
         state.waiting_for_thoughts = true;
         state.thoughts_timer = DEFAULT_THOUGHTS_DELAY;
+        state.currrent_thoughts = None;
+        state.evaluation = 0.0;
+        state.hints_used = [0, 0];
+        state.stop_reason = None;
 
     // If the AI strength buttons are clicked
     } else if ui.settings_elements.ai_1.check_collision_point_rec(offset) {
@@ -207,6 +246,15 @@ fn handle_settings_tab_click(
         ui.state.max_sims = COMPUTER_LEVEL_3_SIMS
             * (COMPUTER_SIM_SCALING.pow((ui.state.depth - 1).try_into().unwrap()));
         ui.state.is_ai_modified = false
+    } else if ui
+        .settings_elements
+        .ai_perfect
+        .check_collision_point_rec(offset)
+    {
+        ui.state.ai_strength = COMPUTER_PERFECT_STRENGTH;
+        ui.state.max_sims = COMPUTER_LEVEL_3_SIMS
+            * (COMPUTER_SIM_SCALING.pow((ui.state.depth - 1).try_into().unwrap()));
+        ui.state.is_ai_modified = false
     } else if ui
         .settings_elements
         .ai_max_sims
@@ -219,6 +267,80 @@ fn handle_settings_tab_click(
         .check_collision_point_rec(offset)
     {
         state.typing = Textbox::MaxTime;
+
+    // Cycle through the engines if an engine button is clicked
+    } else if ui
+        .settings_elements
+        .engine_1
+        .check_collision_point_rec(offset)
+    {
+        ui.state.player_1_engine = ui.state.player_1_engine.next();
+    } else if ui
+        .settings_elements
+        .engine_2
+        .check_collision_point_rec(offset)
+    {
+        ui.state.player_2_engine = ui.state.player_2_engine.next();
+
+    // Cycle through the move selection policies if a policy button is clicked
+    } else if ui
+        .settings_elements
+        .policy_1
+        .check_collision_point_rec(offset)
+    {
+        ui.state.player_1_policy = ui.state.player_1_policy.next();
+    } else if ui
+        .settings_elements
+        .policy_2
+        .check_collision_point_rec(offset)
+    {
+        ui.state.player_2_policy = ui.state.player_2_policy.next();
+
+    // Edit or cycle through the advanced AI settings if one is clicked
+    } else if ui
+        .settings_elements
+        .exploration
+        .check_collision_point_rec(offset)
+    {
+        state.typing = Textbox::Exploration;
+    } else if ui
+        .settings_elements
+        .playout
+        .check_collision_point_rec(offset)
+    {
+        ui.state.playout = ui.state.playout.next();
+    } else if ui
+        .settings_elements
+        .parallelism
+        .check_collision_point_rec(offset)
+    {
+        ui.state.parallelism = ui.state.parallelism.next();
+    } else if ui
+        .settings_elements
+        .heat_map
+        .check_collision_point_rec(offset)
+    {
+        ui.state.heat_map = ui.state.heat_map.next();
+        // The overlay is drawn from the thoughts, so start collecting them
+        state.waiting_for_thoughts = true;
+    } else if ui
+        .settings_elements
+        .ponder
+        .check_collision_point_rec(offset)
+    {
+        ui.state.ponder ^= true;
+        // Any analysis the player wants is restarted by the main loop
+        if !ui.state.ponder {
+            stop_analysis(state);
+        }
+    } else if ui.settings_elements.book.check_collision_point_rec(offset) {
+        ui.state.book = ui.state.book.next();
+    } else if ui
+        .settings_elements
+        .transpositions
+        .check_collision_point_rec(offset)
+    {
+        ui.state.transpositions ^= true;
     } else if ui.settings_elements.rules.check_collision_point_rec(offset) {
         let _ = open_url(RULES_URL);
 
@@ -268,6 +390,7 @@ fn handle_symbols_tab_click(
     mouse_pos: Vector2,
     ui: &mut UI,
     g: &mut Game,
+    state: &mut State,
     rl: &mut RaylibHandle,
     rlthread: &mut RaylibThread,
 ) {
@@ -309,8 +432,8 @@ fn handle_symbols_tab_click(
     }
 
     if update {
-        g.player_1 = get_player_from_symbol(&ui.state.player_1);
-        g.player_2 = get_player_from_symbol(&ui.state.player_2);
-        update_window_title(rl, rlthread, g)
+        state.view.player_1 = get_player_from_symbol(&ui.state.player_1);
+        state.view.player_2 = get_player_from_symbol(&ui.state.player_2);
+        update_window_title(rl, rlthread, g, &state.view)
     }
 }
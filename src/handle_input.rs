@@ -1,4 +1,4 @@
-use std::{fs, time::Duration};
+use std::fs;
 
 use raylib::{
     camera::Camera2D,
@@ -7,17 +7,20 @@ use raylib::{
     RaylibHandle, RaylibThread,
 };
 
+use game::{game::Game, value::Value, Move};
+
 use crate::{
-    ai::{
-        monte_carlo_policy::MonteCarloPolicy, monte_carlo_settings::MonteCarloSettings, noughbert_message::NoughbertMessage
+    common::{
+        get_game_rect, get_ui_rect, interrupt_computers, request_computer_move, request_hint,
+        update_window_title,
     },
-    common::{get_game_rect, get_ui_rect, update_window_title, Move},
-    game::{game::Game, value::Value},
     handle_click::handle_click,
+    render::view::View,
+    saved_game::SavedGame,
     state::State,
     styles::{
-        ALLOW_FPS_COUNTER, CAMERA_MOVE_SPEED, CAMERA_SCROLL_SPEED, DEFAULT_EXPLORATION_FACTOR,
-        UI_DIVIDER_THICKNESS, UI_NAVBAR_HEIGHT, UI_SCROLL_SPEED,
+        ALLOW_FPS_COUNTER, CAMERA_MOVE_SPEED, CAMERA_SCROLL_SPEED, UI_DIVIDER_THICKNESS,
+        UI_NAVBAR_HEIGHT, UI_SCROLL_SPEED,
     },
     ui::{textbox::Textbox, ui::UI, ui_tab::UITab},
 };
@@ -40,7 +43,7 @@ pub fn handle_input(
     }
 
     // Centre the camera
-    g.camera.offset = Vector2 {
+    state.view.camera.offset = Vector2 {
         x: state.game_rect.width / 2.0,
         y: state.game_rect.height / 2.0,
     };
@@ -103,9 +106,9 @@ pub fn handle_input(
         }
     } else {
         // If the mouse is over the Game, increment the Camera zoom
-        g.camera.zoom += x * CAMERA_SCROLL_SPEED * g.camera.zoom;
-        if g.camera.zoom < 0.0 {
-            g.camera.zoom *= -1.0;
+        state.view.camera.zoom += x * CAMERA_SCROLL_SPEED * state.view.camera.zoom;
+        if state.view.camera.zoom < 0.0 {
+            state.view.camera.zoom *= -1.0;
         }
     }
 
@@ -124,21 +127,23 @@ pub fn handle_input(
 
     // Pan when a good right-click is held
     if rl.is_mouse_button_down(MouseButton::MOUSE_BUTTON_RIGHT) && state.good_right_click {
-        g.camera.target.x += (mouse_pos.x - state.mouse_prev.x) * CAMERA_MOVE_SPEED / g.camera.zoom;
-        g.camera.target.y += (mouse_pos.y - state.mouse_prev.y) * CAMERA_MOVE_SPEED / g.camera.zoom;
+        state.view.camera.target.x +=
+            (mouse_pos.x - state.mouse_prev.x) * CAMERA_MOVE_SPEED / state.view.camera.zoom;
+        state.view.camera.target.y +=
+            (mouse_pos.y - state.mouse_prev.y) * CAMERA_MOVE_SPEED / state.view.camera.zoom;
     }
     state.mouse_prev = mouse_pos;
 
     // Get the vurrently hovered-over cell
-    let world_coord = rl.get_screen_to_world2D(mouse_pos, g.camera);
-    let hovered_cell = g.get_cell_from_pixel(world_coord, false);
+    let world_coord = rl.get_screen_to_world2D(mouse_pos, state.view.camera);
+    let hovered_cell = state.view.get_cell_from_pixel(g, world_coord, false);
 
     // Handle left-click inputs
     handle_click(rl, rlthread, g, ui, state, mouse_pos, &hovered_cell);
 
     // Re-centre the camera when enter is pressed
     if rl.is_key_pressed(KeyboardKey::KEY_ENTER) {
-        g.centre_camera(state.game_rect);
+        state.view.centre_camera(state.game_rect);
     }
 
     // When the backspace key is pressed, either delete the last character, or unplay the last move
@@ -153,11 +158,13 @@ pub fn handle_input(
                 let x = &mut ui.state.max_time;
                 *x /= 10;
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x /= 10;
+            }
             Textbox::None => {
                 let _ = g.unplay();
-                state
-                    .message_queue
-                    .insert(state.message_queue.len(), NoughbertMessage::Interrupt);
+                interrupt_computers(state);
                 ui.state.is_ai_modified = true
             }
         }
@@ -168,20 +175,16 @@ pub fn handle_input(
         && g.board.check() == Value::None
         && !state.waiting_for_move
     {
-        state.message_queue.insert(
-            state.message_queue.len(),
-            NoughbertMessage::Start(MonteCarloSettings {
-                game: g.clone(),
-                timeout: Duration::from_secs(ui.state.max_time as u64),
-                max_sims: ui.state.max_sims,
-                threads: ui.state.ai_threads,
-                exploration_factor: DEFAULT_EXPLORATION_FACTOR,
-                opt_for: g.turn,
-                carry_forward: false,
-                policy: MonteCarloPolicy::Robust,
-            }),
-        );
-        state.waiting_for_move = true;
+        request_computer_move(g, &ui.state, state);
+    }
+
+    // Ask for a hint when H is pressed
+    if rl.is_key_pressed(KeyboardKey::KEY_H)
+        && g.board.check() == Value::None
+        && !state.waiting_for_move
+        && state.hint_timer <= 0.0
+    {
+        request_hint(g, &ui.state, state);
     }
 
     // Toggle the FPS counter when the grave key is pressed
@@ -202,31 +205,24 @@ pub fn handle_input(
         let paths = paths.paths();
         let path = paths.last().unwrap();
         let json = fs::read(path).unwrap();
-        match serde_json::from_slice::<Game>(&json) {
-            Ok(new_game) => {
-                *g = Game {
-                    rect: new_game.rect,
+        match serde_json::from_slice::<SavedGame>(&json) {
+            Ok(saved) => {
+                *g = saved.game;
+                state.hints_used = saved.hints;
+                state.view = View {
                     camera: Camera2D {
                         zoom: 1.0,
                         ..Default::default()
                     },
-                    board: new_game.board,
-                    depth: new_game.depth,
-                    turn: new_game.turn,
-                    players: new_game.players,
-                    moves: new_game.moves,
-                    legal: new_game.legal,
-                    player_1: new_game.player_1,
-                    player_2: new_game.player_2,
+                    ..saved.view
                 };
 
                 // Update the state to reflect the new game
-                g.update_positions();
-                g.centre_camera(state.game_rect);
+                state.view.centre_camera(state.game_rect);
                 ui.state.is_ai_modified = true;
-                ui.state.player_1 = g.player_1.symbol;
-                ui.state.player_2 = g.player_2.symbol;
-                update_window_title(rl, rlthread, g);
+                ui.state.player_1 = state.view.player_1.symbol;
+                ui.state.player_2 = state.view.player_2.symbol;
+                update_window_title(rl, rlthread, g, &state.view);
             }
             Err(_) => {
                 println!("Could not read game from file");
@@ -252,6 +248,10 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 let x = &mut ui.state.max_time;
                 *x = x.saturating_mul(10);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+            }
             Textbox::None => {}
         }
     }
@@ -270,6 +270,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(1);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(1);
+            }
             Textbox::None => {}
         }
     }
@@ -288,6 +293,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(2);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(2);
+            }
             Textbox::None => {}
         }
     }
@@ -306,6 +316,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(3);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(3);
+            }
             Textbox::None => {}
         }
     }
@@ -323,6 +338,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(4);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(4);
+            }
             Textbox::None => {}
         }
     }
@@ -341,6 +361,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(5);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(5);
+            }
             Textbox::None => {}
         }
     }
@@ -359,6 +384,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(6);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(6);
+            }
             Textbox::None => {}
         }
     }
@@ -377,6 +407,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(7);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(7);
+            }
             Textbox::None => {}
         }
     }
@@ -395,6 +430,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(8);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(8);
+            }
             Textbox::None => {}
         }
     }
@@ -413,6 +453,11 @@ fn handle_typing(rl: &mut RaylibHandle, state: &mut State, ui: &mut UI) {
                 *x = x.saturating_mul(10);
                 *x = x.saturating_add(9);
             }
+            Textbox::Exploration => {
+                let x = &mut ui.state.exploration_factor;
+                *x = x.saturating_mul(10);
+                *x = x.saturating_add(9);
+            }
             Textbox::None => {}
         }
     }
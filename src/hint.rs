@@ -0,0 +1,12 @@
+use game::Move;
+
+/// A move suggested by the AI for the player to move, which is shown but not played
+#[derive(Debug, Clone)]
+pub struct Hint {
+    /// The hash of the position the hint was given for
+    pub hash: u64,
+    /// The suggested move
+    pub play: Move,
+    /// The expected score of the move, from 0 for a loss to 1 for a win, with draws counting half
+    pub expected_score: f32,
+}
@@ -1,38 +1,45 @@
-use std::{sync::mpsc, thread, time::Duration};
+use std::{sync::mpsc, thread};
 
-use ai::comms::Comms;
+use ai::{
+    bramble::bramble, comms::Comms, engine::Engine, monte_carlo_proof::MonteCarloProof,
+    noughbert::noughbert, noughbert_message::NoughbertMessage, thorn::thorn,
+    thorn_message::ThornMessage,
+};
 use anyhow::Result;
+use game::{
+    game::{Game, Turn},
+    value::Value,
+};
 use raylib::{core::texture::RaylibTexture2D, prelude::*};
 use styles::{
-    BOARD_DEFAULT_DEPTH, BOARD_DEFAULT_PLAYERS, COLOUR_DRAW_FG, COLOUR_UI_BG,
-    COMPUTER_RESPONSE_DELAY, DEFAULT_EXPLORATION_FACTOR, DEFAULT_MAX_TIME,
-    DEFAULT_SHOW_FPS_COUNTER, DEFAULT_THOUGHTS_DELAY, UI_PANEL_MIN_HEIGHT, UI_PANEL_WIDTH,
+    BOARD_DEFAULT_DEPTH, BOARD_DEFAULT_PLAYERS, COLOUR_DRAW_FG, COLOUR_HINT, COMPUTER_RESPONSE_DELAY,
+    DEFAULT_MAX_TIME, DEFAULT_SHOW_FPS_COUNTER, DEFAULT_THOUGHTS_DELAY, EVALUATION_ANALYSIS_TIME,
+    UI_PANEL_MIN_HEIGHT, UI_PANEL_WIDTH,
 };
 
 use crate::{
-    ai::{
-        noughbert_message::NoughbertMessage, monte_carlo_policy::MonteCarloPolicy,
-        monte_carlo_settings::MonteCarloSettings, noughbert::noughbert,
-    },
     common::{
-        get_board_rect, get_game_rect, get_player_from_symbol, get_ui_rect, update_window_title,
+        draw_evaluation_bar, get_board_rect, get_game_rect, get_player_from_symbol, get_ui_rect,
+        request_computer_move, start_analysis, stop_analysis, take_hint, update_window_title,
+        winner_moves,
     },
     fonts::Fonts,
-    game::{
-        game::{Game, Turn},
-        value::Value,
-    },
     handle_input::handle_input,
+    render::{
+        heat_map::{draw_heat_map_details, heat_map},
+        view::View,
+    },
     state::State,
     ui::{textbox::Textbox, ui::UI},
 };
 
-mod ai;
 mod common;
 mod fonts;
-mod game;
 mod handle_click;
 mod handle_input;
+mod hint;
+mod render;
+mod saved_game;
 mod state;
 mod styles;
 mod ui;
@@ -50,6 +57,30 @@ fn main() -> Result<()> {
 
     let noughbert = Comms::new(rx_1, tx_0);
 
+    // Main thread comms with Thorn
+    let (tx_2, rx_2) = mpsc::sync_channel::<ThornMessage>(0);
+
+    // Thorn comms with main thread
+    let (tx_3, rx_3) = mpsc::sync_channel::<ThornMessage>(1);
+
+    let _thorn_thread = thread::spawn(move || {
+        thorn(Comms::new(rx_2, tx_3));
+    });
+
+    let thorn = Comms::new(rx_3, tx_2);
+
+    // Main thread comms with Bramble
+    let (tx_4, rx_4) = mpsc::sync_channel::<NoughbertMessage>(0);
+
+    // Bramble comms with main thread
+    let (tx_5, rx_5) = mpsc::sync_channel::<NoughbertMessage>(1);
+
+    let _bramble_thread = thread::spawn(move || {
+        bramble(Comms::new(rx_4, tx_5));
+    });
+
+    let bramble = Comms::new(rx_5, tx_4);
+
     // Initialise Raylib
     let (mut rl, mut thread) = raylib::init()
         .size(650 * 2, 650 * 2)
@@ -90,11 +121,7 @@ fn main() -> Result<()> {
     );
 
     // Create the game
-    let mut g = Game::new_depth(
-        get_board_rect(BOARD_DEFAULT_DEPTH),
-        BOARD_DEFAULT_DEPTH,
-        BOARD_DEFAULT_PLAYERS,
-    );
+    let mut g = Game::new_depth(BOARD_DEFAULT_DEPTH, BOARD_DEFAULT_PLAYERS);
 
     // Create the ui
     let mut ui = UI::new();
@@ -105,12 +132,24 @@ fn main() -> Result<()> {
         good_right_click: false,
         show_fps: DEFAULT_SHOW_FPS_COUNTER,
         waiting_for_move: false,
-        waiting_for_thoughts: false,
+        waiting_for_thoughts: true,
         move_delay: 0.0,
         thoughts_timer: 0.0,
         message_queue: vec![],
+        thorn_queue: vec![],
+        bramble_queue: vec![],
         move_queue: vec![],
         currrent_thoughts: None,
+        analysing: false,
+        analysis_hash: None,
+        analysis_engine: Engine::Noughbert,
+        evaluated_hash: None,
+        evaluation_timer: 0.0,
+        evaluation: 0.0,
+        hint: None,
+        hint_timer: 0.0,
+        hints_used: [0, 0],
+        stop_reason: None,
         typing: Textbox::None,
         can_export: true,
         num_cpus: num_cpus::get(),
@@ -120,19 +159,19 @@ fn main() -> Result<()> {
             regular: font_50pt,
             bold: font_50pt_bold,
         },
+        view: View::new(get_board_rect(BOARD_DEFAULT_DEPTH)),
     };
 
-    // Get the pixel positions of each cell in the game, and each element in the UI
-    g.update_positions();
+    // Get the pixel positions of each element in the UI
     ui.update_positions(state.ui_rect);
 
     // Centre the camera
-    g.centre_camera(state.game_rect);
+    state.view.centre_camera(state.game_rect);
 
     // Load the symbols
-    g.player_1 = get_player_from_symbol(&ui.state.player_1);
-    g.player_2 = get_player_from_symbol(&ui.state.player_2);
-    update_window_title(&mut rl, &mut thread, &g);
+    state.view.player_1 = get_player_from_symbol(&ui.state.player_1);
+    state.view.player_2 = get_player_from_symbol(&ui.state.player_2);
+    update_window_title(&mut rl, &mut thread, &g, &state.view);
 
     ui.state.ai_threads = state.num_cpus;
 
@@ -152,28 +191,82 @@ fn main() -> Result<()> {
             && g.board.check() == Value::None
             && !state.waiting_for_move
         {
-            state.message_queue.insert(
-                state.message_queue.len(),
-                NoughbertMessage::Start(MonteCarloSettings {
-                    game: g.clone(),
-                    timeout: Duration::from_secs(ui.state.max_time as u64),
-                    max_sims: ui.state.max_sims,
-                    threads: ui.state.ai_threads,
-                    exploration_factor: DEFAULT_EXPLORATION_FACTOR,
-                    opt_for: g.turn,
-                    carry_forward: false,
-                    policy: MonteCarloPolicy::Robust,
-                }),
-            );
-            state.waiting_for_move = true;
+            request_computer_move(&g, &ui.state, &mut state);
+        }
+
+        // Keep any analysis on the current position by the engine of the player to move, restarting it
+        // whenever either changes
+        let engine = ui.state.engine(g.turn);
+        if state.analysing
+            && !state.waiting_for_move
+            && g.board.check() == Value::None
+            && (state.analysis_hash != Some(g.hash) || state.analysis_engine != engine)
+        {
+            start_analysis(&g, engine, &ui.state, &mut state);
+        }
+
+        // In 1-player games, let Noughbert search while the human decides, carrying the tree into its reply
+        let ponder = ui.state.ponder
+            && g.players == 1
+            && g.turn == Turn::Player1
+            && ui.state.engine(Turn::Player2) == Engine::Noughbert
+            && g.board.check() == Value::None;
+        if ponder
+            && !state.analysing
+            && !state.waiting_for_move
+            && state.analysis_hash != Some(g.hash)
+        {
+            start_analysis(&g, Engine::Noughbert, &ui.state, &mut state);
+        }
+
+        // In 2-player games, briefly analyse each new position to keep the evaluation bar up to date
+        if g.players == 2
+            && !state.analysing
+            && !state.waiting_for_move
+            && g.board.check() == Value::None
+            && state.evaluated_hash != Some(g.hash)
+        {
+            start_analysis(&g, engine, &ui.state, &mut state);
+            state.evaluated_hash = Some(g.hash);
+            state.evaluation_timer = EVALUATION_ANALYSIS_TIME;
+        }
+        if state.evaluation_timer > 0.0 {
+            state.evaluation_timer -= delta;
+            if state.evaluation_timer <= 0.0 && !state.analysing && state.hint_timer <= 0.0 {
+                stop_analysis(&mut state);
+            }
+        }
+
+        // Once the AI has thought about a hint for long enough, take its favourite move
+        if state.hint_timer > 0.0 {
+            state.hint_timer -= delta;
+            if state.analysis_hash != Some(g.hash) {
+                // The position changed (or the AI was interrupted) before the hint was ready
+                state.hint_timer = 0.0;
+            } else if state.hint_timer <= 0.0 {
+                state.hint = take_hint(&g, &state);
+                if !state.analysing && !ponder && state.evaluation_timer <= 0.0 {
+                    stop_analysis(&mut state);
+                }
+            }
+        }
+        if state.hint.as_ref().is_some_and(|x| x.hash != g.hash) {
+            state.hint = None;
         }
 
         // Send all queued messages
         for message in state.message_queue.drain(0..state.message_queue.len()) {
             noughbert.send(message).unwrap();
         }
+        for message in state.thorn_queue.drain(0..state.thorn_queue.len()) {
+            thorn.send(message).unwrap();
+        }
+        for message in state.bramble_queue.drain(0..state.bramble_queue.len()) {
+            bramble.send(message).unwrap();
+        }
 
-        // Recieve any sent messages, and queue all moves
+        // Recieve any sent messages, and queue all moves. While an analysis runs, only its engine's
+        // thoughts are kept
         loop {
             let msg = noughbert.try_recv();
             match msg {
@@ -182,13 +275,16 @@ fn main() -> Result<()> {
                     NoughbertMessage::Return() => {}
                     NoughbertMessage::GetThoughts(_) => {}
                     NoughbertMessage::Thoughts(th) => {
-                        if state.waiting_for_thoughts {
+                        if state.waiting_for_thoughts
+                            && (state.analysis_hash.is_none() || state.analysis_engine == Engine::Noughbert)
+                        {
                             state.currrent_thoughts = Some(th);
                             // println!("{:?}", state.currrent_thoughts);
                         }
                     }
-                    NoughbertMessage::Move(mv) => {
+                    NoughbertMessage::Move(mv, reason) => {
                         if state.waiting_for_move {
+                            state.stop_reason = Some(reason);
                             if let Some(y) = mv {
                                 state.move_queue.insert(0, y);
                                 // println!("{:?}", state.move_queue);
@@ -203,6 +299,78 @@ fn main() -> Result<()> {
                 },
             }
         }
+        loop {
+            let msg = thorn.try_recv();
+            match msg {
+                Ok(msg) => match msg {
+                    ThornMessage::Start(_) => {}
+                    ThornMessage::Return() => {}
+                    ThornMessage::GetThoughts(_) => {}
+                    ThornMessage::Thoughts(th) => {
+                        if state.waiting_for_thoughts
+                            && (state.analysis_hash.is_none() || state.analysis_engine == Engine::Thorn)
+                        {
+                            state.currrent_thoughts = Some(th);
+                        }
+                    }
+                    ThornMessage::Move(mv, reason) => {
+                        if state.waiting_for_move {
+                            state.stop_reason = Some(reason);
+                            if let Some(y) = mv {
+                                state.move_queue.insert(0, y);
+                            }
+                        }
+                    }
+                    ThornMessage::Clip(_) => {}
+                    ThornMessage::Interrupt => {}
+                },
+                Err(e) => match e {
+                    mpsc::TryRecvError::Empty => break,
+                    mpsc::TryRecvError::Disconnected => panic!("Thread disconnected"),
+                },
+            }
+        }
+        loop {
+            let msg = bramble.try_recv();
+            match msg {
+                Ok(msg) => match msg {
+                    NoughbertMessage::Start(_) => {}
+                    NoughbertMessage::Return() => {}
+                    NoughbertMessage::GetThoughts(_) => {}
+                    NoughbertMessage::Thoughts(th) => {
+                        if state.waiting_for_thoughts
+                            && (state.analysis_hash.is_none() || state.analysis_engine == Engine::Bramble)
+                        {
+                            state.currrent_thoughts = Some(th);
+                        }
+                    }
+                    NoughbertMessage::Move(mv, reason) => {
+                        if state.waiting_for_move {
+                            state.stop_reason = Some(reason);
+                            if let Some(y) = mv {
+                                state.move_queue.insert(0, y);
+                            }
+                        }
+                    }
+                    NoughbertMessage::Interrupt => {}
+                },
+                Err(e) => match e {
+                    mpsc::TryRecvError::Empty => break,
+                    mpsc::TryRecvError::Disconnected => panic!("Thread disconnected"),
+                },
+            }
+        }
+
+        // Follow the latest thoughts in the evaluation bar, taking a proven result as certain
+        if let Some(t) = &state.currrent_thoughts {
+            state.evaluation = match t.proven {
+                Some(MonteCarloProof::Win(_)) => 1.0,
+                Some(MonteCarloProof::Loss(_)) => -1.0,
+                Some(MonteCarloProof::Draw) => 0.0,
+                None if t.sims > 0 => t.score / t.sims as f32,
+                None => state.evaluation,
+            };
+        }
 
         // If the delay between moves is 0, play the next queued move
         if state.move_delay <= 0.0 {
@@ -210,18 +378,21 @@ fn main() -> Result<()> {
                 // println!("Some move");
                 // dbg!(&state.move_queue);
                 g.play(&mv).unwrap();
+                state
+                    .thorn_queue
+                    .insert(state.thorn_queue.len(), ThornMessage::Clip(mv));
                 state.waiting_for_move = false;
             }
         }
 
         let gr = get_game_rect(&rl);
-        let real_origin = rl.get_screen_to_world2D(Vector2 { x: gr.x, y: gr.y }, g.camera);
+        let real_origin = rl.get_screen_to_world2D(Vector2 { x: gr.x, y: gr.y }, state.view.camera);
         let real_maximum = rl.get_screen_to_world2D(
             Vector2 {
                 x: gr.x + gr.width,
                 y: gr.y + gr.height,
             },
-            g.camera,
+            state.view.camera,
         );
         let on_screen_rect = Rectangle {
             x: real_origin.x,
@@ -230,6 +401,25 @@ fn main() -> Result<()> {
             height: real_maximum.y - real_origin.y,
         };
 
+        // Tint the moves being searched, if the overlay is on and Noughbert is thinking
+        let thinking = state.waiting_for_move || state.analysis_hash.is_some();
+        let mut heat = match &state.currrent_thoughts {
+            Some(t) if thinking => heat_map(
+                t,
+                &g,
+                ui.state.heat_map,
+                &state.view.player_1,
+                &state.view.player_2,
+            ),
+            _ => vec![],
+        };
+        // Highlight the hinted cell over the heat map
+        if let Some(hint) = &state.hint {
+            heat.retain(|(pos, _)| pos != &hint.play);
+            heat.push((hint.play.clone(), COLOUR_HINT));
+        }
+        let mouse_pos = rl.get_mouse_position();
+
         let mut d = rl.begin_drawing(&thread);
 
         // Set the background
@@ -238,16 +428,24 @@ fn main() -> Result<()> {
         // let world_coord = rl.get_screen_to_world2D(mouse_pos, g.camera);
 
         // Draw the game
-        g.draw(
+        state.view.draw(
+            &g,
             get_board_rect(g.depth),
             &on_screen_rect,
-            &gr,
             &mut d,
             false,
             true,
             hovered_cell.as_deref(),
+            &heat,
         );
 
+        // Show the exact statistics of a tinted cell under the mouse
+        if let (Some(t), Some(hovered)) = (&state.currrent_thoughts, &hovered_cell) {
+            if heat.iter().any(|(pos, _)| pos == hovered) {
+                draw_heat_map_details(&mut d, t, &g, hovered, mouse_pos, &state.fonts);
+            }
+        }
+
         // Draw the UI
         ui.draw(state.ui_rect, &mut d, &g, &state);
 
@@ -256,36 +454,69 @@ fn main() -> Result<()> {
             d.draw_fps(10, 10);
         }
 
-        match state.currrent_thoughts {
-            Some(t) => {
-                d.draw_text(&format!("{}", t.sims), 50, 50, 20, Color::RAYWHITE);
-                if t.score > 0.0 {
-                    d.draw_text(
-                        &format!("{}", t.score / t.sims as f32),
-                        10,
-                        80,
-                        20,
-                        g.player_1.foreground,
-                    );
-                } else if t.score < 0.0 {
+        // Draw the evaluation bar
+        draw_evaluation_bar(
+            &mut d,
+            state.game_rect,
+            state.evaluation,
+            &state.view.player_1,
+            &state.view.player_2,
+        );
+
+        // Announce a proven result, counting only the winner's own moves
+        if let Some(t) = &state.currrent_thoughts {
+            match t.proven {
+                Some(MonteCarloProof::Win(n)) => {
                     d.draw_text(
-                        &format!("{}", t.score / t.sims as f32),
+                        &format!("Mate in {}", winner_moves(n, g.turn == Turn::Player1)),
                         10,
-                        80,
+                        50,
                         20,
-                        g.player_2.foreground,
+                        state.view.player_1.foreground,
                     );
-                } else {
+                }
+                Some(MonteCarloProof::Loss(n)) => {
                     d.draw_text(
-                        &format!("{}", t.score / t.sims as f32),
+                        &format!("Mate in {}", winner_moves(n, g.turn == Turn::Player2)),
                         10,
-                        80,
+                        50,
                         20,
-                        COLOUR_UI_BG,
+                        state.view.player_2.foreground,
                     );
                 }
+                Some(MonteCarloProof::Draw) => d.draw_text("Proven draw", 10, 50, 20, COLOUR_DRAW_FG),
+                None => {}
+            }
+        }
+
+        // Show why the computer stopped thinking about its last move
+        if let Some(reason) = state.stop_reason {
+            d.draw_text(
+                &format!("Stopped: {}", reason.name()),
+                10,
+                80,
+                20,
+                COLOUR_DRAW_FG,
+            );
+        }
+
+        // Show how much of the search was spent on leaves that were already being simulated
+        if let Some(t) = &state.currrent_thoughts {
+            if t.started > 0 {
+                d.draw_text(
+                    &format!(
+                        "Sims: {}   Duplicate leaves: {} of {} ({:.1}%)",
+                        t.sims,
+                        t.duplicate_leaves,
+                        t.started,
+                        100.0 * t.duplicate_leaves as f32 / t.started as f32
+                    ),
+                    10,
+                    110,
+                    20,
+                    COLOUR_DRAW_FG,
+                );
             }
-            None => d.draw_text("None", 10, 50, 20, Color::RED),
         }
 
         // Decrement the response delay by the frame time
@@ -297,7 +528,9 @@ fn main() -> Result<()> {
         state.thoughts_timer -= delta;
         if state.thoughts_timer < 0.0 {
             state.thoughts_timer = DEFAULT_THOUGHTS_DELAY;
-            noughbert.send(NoughbertMessage::GetThoughts(Turn::Player2)).unwrap()
+            noughbert.send(NoughbertMessage::GetThoughts(Turn::Player1)).unwrap();
+            thorn.send(ThornMessage::GetThoughts(Turn::Player1)).unwrap();
+            bramble.send(NoughbertMessage::GetThoughts(Turn::Player1)).unwrap();
         }
     }
 
@@ -0,0 +1,234 @@
+use game::{board::Board, cell::Cell, game::Turn, value::Value, Move};
+use raylib::{core::math::Rectangle, prelude::*};
+
+use crate::{
+    common::{get_cell_rects, get_greyed_colour_board},
+    styles::{BOARD_LINE_THICK, COLOUR_BOARD_BG, COLOUR_BOARD_FG, INVERT_GREYS},
+};
+
+use super::{cell::CellRender, legal::Legal, player::Player};
+
+/// Draws a `Board` with raylib, and maps pixels back onto its cells
+pub trait BoardRender {
+    /// Returns the cell under `point`, given that the board is drawn inside `rect`
+    fn get_cell_from_pixel(&self, rect: Rectangle, point: Vector2, no_check: bool) -> Option<Move>;
+
+    /// Draws the board in a given `Rectangle`. Automatically checking for wins can be turned off, as well as rendering completed boards under their symbols.
+    /// Cells in `heat` are tinted, given by their position relative to this board
+    fn draw<T: RaylibDraw>(
+        &self,
+        rect: Rectangle,
+        on_screen_rect: &Rectangle,
+        d: &mut T,
+        no_check: bool,
+        alpha: bool,
+        hover: Option<&[usize]>,
+        heat: &[(&[usize], Color)],
+        legal: Legal,
+        turn: Turn,
+        player_1: &Player,
+        player_2: &Player,
+    );
+}
+
+impl BoardRender for Board {
+    fn get_cell_from_pixel(&self, rect: Rectangle, point: Vector2, no_check: bool) -> Option<Move> {
+        // Iterate over every cell in the board.
+        for ((cell, rect), i) in self.cells.iter().zip(get_cell_rects(rect)).zip(0..9) {
+            // If the point collides with the cell...
+            if rect.check_collision_point_rec(point) {
+                // ...and it is a board...
+                if let Cell::Board(b) = cell {
+                    // ...and it hasn't been completed (or we don't check)...
+                    if (b.check() == Value::None) || no_check {
+                        // ...then append the current coordinate...
+                        let mut out = vec![i];
+                        let x = b.get_cell_from_pixel(rect, point, no_check);
+                        match x {
+                            Some(mut x) => {
+                                out.append(&mut x);
+                                return Some(out);
+                            }
+                            None => return None,
+                        }
+                    } else {
+                        return Some(vec![i]);
+                    }
+                } else {
+                    return Some(vec![i]);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn draw<T: RaylibDraw>(
+        &self,
+        rect: Rectangle,
+        on_screen_rect: &Rectangle,
+        d: &mut T,
+        no_check: bool,
+        alpha: bool,
+        hover: Option<&[usize]>,
+        heat: &[(&[usize], Color)],
+        mut legal: Legal,
+        turn: Turn,
+        player_1: &Player,
+        player_2: &Player,
+    ) {
+        let minsize_x = 100.0 / on_screen_rect.width;
+        let minsize_y = 100.0 / on_screen_rect.height;
+        if rect.width < minsize_x || rect.height < minsize_y {
+            return;
+        }
+
+        if !on_screen_rect.check_collision_recs(&rect)
+            || rect.width < minsize_x
+            || rect.height < minsize_y
+        {
+            return;
+        }
+        let mut t: Option<usize> = None;
+        let mut ignore = false;
+        if legal == Legal::ForceDefaultBg {
+            t = Some(13);
+            ignore = true;
+        } else if let Legal::Pos(x) = legal {
+            if !x.is_empty() {
+                t = Some(x[0]);
+                if x.len() == 1 {
+                    legal = Legal::Pos(&[]);
+                } else {
+                    legal = Legal::Pos(&x[1..]);
+                }
+            } else {
+                t = Some(10);
+            }
+        };
+
+        let board_complete = self.check() != Value::None || ignore;
+
+        d.draw_rectangle_rec(
+            rect,
+            if board_complete {
+                COLOUR_BOARD_BG
+            } else if INVERT_GREYS {
+                if t.is_some() {
+                    COLOUR_BOARD_BG
+                } else {
+                    get_greyed_colour_board(turn, player_1, player_2)
+                }
+            } else if let Some(x) = t {
+                if x == 10 {
+                    get_greyed_colour_board(turn, player_1, player_2)
+                } else {
+                    COLOUR_BOARD_BG
+                }
+            } else {
+                COLOUR_BOARD_BG
+            },
+        );
+
+        let length = rect.width; // Side length of the board
+        let thickness = BOARD_LINE_THICK * rect.width; // Thickness of the lines in pixels
+
+        let column_size = (length - 2.0 * thickness) / 3.0;
+        let g1 = column_size + 0.5 * thickness;
+        let g2 = column_size + thickness;
+
+        d.draw_line_ex(
+            // Draw the first vertical line
+            Vector2 {
+                x: rect.x + g1,
+                y: rect.y,
+            },
+            Vector2 {
+                x: rect.x + g1,
+                y: rect.y + rect.height,
+            },
+            thickness,
+            COLOUR_BOARD_FG,
+        );
+
+        d.draw_line_ex(
+            // Draw the second vertical line
+            Vector2 {
+                x: rect.x + g1 + g2,
+                y: rect.y,
+            },
+            Vector2 {
+                x: rect.x + g1 + g2,
+                y: rect.y + rect.height,
+            },
+            thickness,
+            COLOUR_BOARD_FG,
+        );
+
+        d.draw_line_ex(
+            // Draw the first horizontal line
+            Vector2 {
+                x: rect.x,
+                y: rect.y + g1,
+            },
+            Vector2 {
+                x: rect.x + rect.width,
+                y: rect.y + g1,
+            },
+            thickness,
+            COLOUR_BOARD_FG,
+        );
+
+        d.draw_line_ex(
+            // Draw the second horizontal line
+            Vector2 {
+                x: rect.x,
+                y: rect.y + g1 + g2,
+            },
+            Vector2 {
+                x: rect.x + rect.width,
+                y: rect.y + g1 + g2,
+            },
+            thickness,
+            COLOUR_BOARD_FG,
+        );
+
+        let mut x = 10;
+        if let Some(pos) = hover {
+            x = pos[0];
+        }
+
+        let cell_positions = get_cell_rects(rect);
+        for i in 0..9 {
+            // Pass each cell only the tints that lie within it
+            let cell_heat: Vec<(&[usize], Color)> = heat
+                .iter()
+                .filter(|(pos, _)| pos.first() == Some(&i))
+                .map(|(pos, colour)| (&pos[1..], *colour))
+                .collect();
+            self.cells[i].draw(
+                cell_positions[i],
+                on_screen_rect,
+                d,
+                no_check,
+                alpha,
+                if i == x {
+                    Some(&hover.unwrap()[1..])
+                } else {
+                    None
+                },
+                &cell_heat,
+                if board_complete {
+                    Legal::ForceDefaultBg
+                } else if [10, i].contains(&t.unwrap_or(11)) {
+                    legal
+                } else {
+                    Legal::None
+                },
+                turn,
+                player_1,
+                player_2,
+            );
+        }
+    }
+}
@@ -1,64 +1,31 @@
-use raylib::{drawing::RaylibDraw, math::Rectangle};
-use serde::{Deserialize, Serialize};
+use game::{cell::Cell, game::Turn, value::Value};
+use raylib::{color::Color, drawing::RaylibDraw, math::Rectangle};
 
-use crate::{
-    common::*, styles::*
-};
+use crate::{common::*, styles::*};
 
-use super::{board::Board, game::Turn, legal::Legal, player::Player, value::Value};
+use super::{board::BoardRender, legal::Legal, player::Player, value::ValueRender};
 
-/// An enum used to differentiate the states of a cell.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub enum Cell {
-    /// An empty cell
-    None,
-    /// The first player
-    Player1,
-    /// The second player
-    Player2,
-    /// Another board
-    Board(Board),
+/// Draws a `Cell` with raylib
+pub trait CellRender {
+    /// Draws the value onto `T`, inside the given `Rectangle`
+    fn draw<T: RaylibDraw>(
+        &self,
+        rect: Rectangle,
+        on_screen_rect: &Rectangle,
+        d: &mut T,
+        no_check: bool,
+        alpha: bool,
+        hover: Option<&[usize]>,
+        heat: &[(&[usize], Color)],
+        legal: Legal,
+        turn: Turn,
+        player_1: &Player,
+        player_2: &Player,
+    );
 }
 
-impl Cell {
-    /// Returns the `Value` corresponding to a cell
-    pub fn value(&self) -> Value {
-        match self {
-            Cell::None => Value::None,
-            Cell::Player1 => Value::Player1,
-            Cell::Player2 => Value::Player2,
-            Cell::Board(b) => b.check(), // If the cell is a `Cell::Board`, return the value of the board instead
-        }
-    }
-
-    /// Returns the possible moves within a cell
-    pub fn moves<'a>(&'a self, pos: &'a [usize]) -> Vec<Move> {
-        match self {
-            Cell::None => vec![pos.to_vec()],
-            Cell::Player1 => vec![pos.to_vec()],
-            Cell::Player2 => vec![pos.to_vec()],
-            Cell::Board(b) => b.moves(pos),
-        }
-    }
-
-    /// Returns the possible legal moves within a cell
-    pub fn legal_moves<'a>(&'a self, pos: &'a [usize]) -> Vec<Move> {
-        match self {
-            Cell::None => vec![pos.to_vec()],
-            Cell::Player1 => vec![],
-            Cell::Player2 => vec![],
-            Cell::Board(b) => {
-                if b.check() != Value::None {
-                    vec![]
-                } else {
-                    b.legal_moves(pos)
-                }
-            }
-        }
-    }
-
-    /// Draws the value onto `T`, inside the given `Rectangle`
-    pub fn draw<T: RaylibDraw>(
+impl CellRender for Cell {
+    fn draw<T: RaylibDraw>(
         &self,
         rect: Rectangle,
         on_screen_rect: &Rectangle,
@@ -66,6 +33,7 @@ impl Cell {
         no_check: bool,
         alpha: bool,
         mut hover: Option<&[usize]>,
+        heat: &[(&[usize], Color)],
         legal: Legal,
         turn: Turn,
         player_1: &Player,
@@ -165,6 +133,7 @@ impl Cell {
                         no_check,
                         alpha,
                         hover,
+                        heat,
                         legal,
                         turn,
                         player_1,
@@ -178,6 +147,7 @@ impl Cell {
                         no_check,
                         alpha,
                         hover,
+                        heat,
                         legal,
                         turn,
                         player_1,
@@ -191,6 +161,7 @@ impl Cell {
                         no_check,
                         alpha,
                         hover,
+                        heat,
                         legal,
                         turn,
                         player_1,
@@ -202,6 +173,10 @@ impl Cell {
             }
         }
 
+        if let Some((_, colour)) = heat.iter().find(|(pos, _)| pos.is_empty()) {
+            d.draw_rectangle_rec(rect, *colour);
+        }
+
         if flag {
             d.draw_rectangle_rec(rect, COLOUR_CELL_HOVER);
         }
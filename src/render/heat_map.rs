@@ -0,0 +1,134 @@
+use ai::thoughts::Thoughts;
+use game::{
+    game::{Game, Turn},
+    Move,
+};
+use raylib::{
+    color::Color,
+    drawing::RaylibDraw,
+    math::{Rectangle, Vector2},
+};
+
+use crate::{
+    fonts::Fonts,
+    styles::{COLOUR_HEAT_MAP_ALPHA, COLOUR_UI_ELEMENT},
+};
+
+use super::player::Player;
+
+/// What the overlay on the board shows for each move being searched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeatMapMode {
+    /// No overlay is drawn
+    Off,
+    /// Cells are tinted by their share of the simulations
+    Visits,
+    /// Cells are tinted by their mean score, in the colour of the player it favours
+    Mean,
+}
+
+impl HeatMapMode {
+    pub fn name(&self) -> String {
+        match self {
+            HeatMapMode::Off => "Off".to_owned(),
+            HeatMapMode::Visits => "Visits".to_owned(),
+            HeatMapMode::Mean => "Mean".to_owned(),
+        }
+    }
+
+    pub fn next(self) -> HeatMapMode {
+        match self {
+            HeatMapMode::Off => Self::Visits,
+            HeatMapMode::Visits => Self::Mean,
+            HeatMapMode::Mean => Self::Off,
+        }
+    }
+}
+
+/// Returns the tint of each legal move in `thoughts`, which are relative to the first player
+pub fn heat_map(
+    thoughts: &Thoughts,
+    g: &Game,
+    mode: HeatMapMode,
+    player_1: &Player,
+    player_2: &Player,
+) -> Vec<(Move, Color)> {
+    let legal = g.legal_moves();
+    let most = thoughts.moves.iter().map(|x| x.sims).max().unwrap_or(0);
+    if mode == HeatMapMode::Off || most == 0 {
+        return vec![];
+    }
+    let mover = if g.turn == Turn::Player1 {
+        player_1
+    } else {
+        player_2
+    };
+
+    thoughts
+        .moves
+        .iter()
+        // The thoughts may still be from the last position for a moment
+        .filter(|x| legal.contains(&x.play))
+        .map(|x| {
+            let (colour, strength) = match mode {
+                HeatMapMode::Visits => (mover.foreground, x.sims as f32 / most as f32),
+                _ if x.mean >= 0.0 => (player_1.foreground, x.mean),
+                _ => (player_2.foreground, -x.mean),
+            };
+            let tint = Color {
+                a: (strength * COLOUR_HEAT_MAP_ALPHA as f32) as u8,
+                ..colour
+            };
+            (x.play.clone(), tint)
+        })
+        .collect()
+}
+
+/// Draws the exact statistics of the hovered move next to the mouse, relative to the player to move
+pub fn draw_heat_map_details<T: RaylibDraw>(
+    d: &mut T,
+    thoughts: &Thoughts,
+    g: &Game,
+    hovered: &Move,
+    mouse: Vector2,
+    fonts: &Fonts,
+) {
+    let Some(m) = thoughts.moves.iter().find(|x| &x.play == hovered) else {
+        return;
+    };
+    let total: usize = thoughts.moves.iter().map(|x| x.sims).sum();
+    let mean = if g.turn == Turn::Player1 {
+        m.mean
+    } else {
+        -m.mean
+    };
+    let lines = [
+        format!(
+            "Visits: {} ({:.1}%)",
+            m.sims,
+            100.0 * m.sims as f32 / total.max(1) as f32
+        ),
+        format!("Mean: {mean:+.3}"),
+    ];
+
+    let rect = Rectangle {
+        x: mouse.x + 20.0,
+        y: mouse.y + 20.0,
+        width: 300.0,
+        height: 90.0,
+    };
+    d.draw_rectangle_rec(rect, COLOUR_UI_ELEMENT);
+    for (i, text) in lines.iter().enumerate() {
+        d.draw_text_ex(
+            &fonts.regular,
+            text,
+            Vector2 {
+                x: rect.x + 10.0,
+                y: rect.y + 10.0 + 35.0 * i as f32,
+            },
+            30.0,
+            0.0,
+            Color::BLACK,
+        );
+    }
+}
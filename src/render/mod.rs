@@ -1,7 +1,8 @@
 pub mod board;
 pub mod cell;
-pub mod game;
+pub mod heat_map;
 pub mod legal;
 pub mod player;
 pub mod symbol;
 pub mod value;
+pub mod view;
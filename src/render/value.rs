@@ -1,3 +1,4 @@
+use game::{game::Turn, value::Value};
 use raylib::{drawing::RaylibDraw, math::Rectangle};
 
 use crate::{
@@ -5,24 +6,25 @@ use crate::{
     styles::{COLOUR_CELL_BG, COLOUR_DRAW_BG, COLOUR_DRAW_BGA},
 };
 
-use super::{game::Turn, legal::Legal, player::Player};
+use super::{legal::Legal, player::Player};
 
-/// An enum used to differentiate the states of a board.
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum Value {
-    /// The board is still being played
-    None,
-    /// The board is drawn
-    Draw,
-    /// The first player has won
-    Player1,
-    /// The second player has won
-    Player2,
+/// Draws a `Value` with raylib
+pub trait ValueRender {
+    /// Draws the value onto `T`, inside the given `Rectangle`
+    fn draw<T: RaylibDraw>(
+        &self,
+        rect: Rectangle,
+        d: &mut T,
+        alpha: bool,
+        legal: Legal,
+        turn: Turn,
+        player_1: &Player,
+        player_2: &Player,
+    );
 }
 
-impl Value {
-    /// Draws the value onto `T`, inside the given `Rectangle`
-    pub fn draw<T: RaylibDraw>(
+impl ValueRender for Value {
+    fn draw<T: RaylibDraw>(
         &self,
         rect: Rectangle,
         d: &mut T,
@@ -0,0 +1,137 @@
+use game::{
+    game::{Game, Turn},
+    value::Value,
+    Move,
+};
+use raylib::{
+    camera::Camera2D,
+    color::Color,
+    drawing::{RaylibDraw, RaylibMode2DExt},
+    math::Rectangle,
+    prelude::Vector2,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::styles::{
+    BOARD_CELL_MARGIN, CAMERA_DEFAULT_ZOOM, COLOUR_BOARD_BG, COLOUR_BOARD_BG_GREYED, CROSS, THORN,
+};
+
+use super::{board::BoardRender, legal::Legal, player::Player};
+
+/// Everything needed to draw a `Game` that the rules themselves don't care about
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct View {
+    /// The rectangle in which the board is rendered to the camera
+    pub rect: Rectangle,
+    #[serde(skip)]
+    /// The camera
+    pub camera: Camera2D,
+    /// Cell renderer for Player 1
+    pub player_1: Player,
+    /// Cell renderer for Player 2
+    pub player_2: Player,
+}
+
+impl View {
+    /// Constructs a new view for a board drawn into `rect`
+    pub fn new(rect: Rectangle) -> Self {
+        View {
+            rect,
+            camera: Camera2D {
+                zoom: 1.0,
+                ..Default::default()
+            },
+            player_1: CROSS,
+            player_2: THORN,
+        }
+    }
+
+    /// Returns the rectangle that the top level board is drawn into, inside the margin of `rect`
+    fn inner_rect(rect: Rectangle) -> Rectangle {
+        let m = rect.width * BOARD_CELL_MARGIN;
+
+        Rectangle {
+            x: rect.x + m,
+            y: rect.x + m,
+            width: rect.width - 2.0 * m,
+            height: rect.height - 2.0 * m,
+        }
+    }
+
+    /// Centres the game camera
+    pub fn centre_camera(&mut self, rect: Rectangle) {
+        self.camera.target = Vector2 {
+            x: self.rect.x + self.rect.width / 2.0f32,
+            y: self.rect.y + self.rect.height / 2.0f32,
+        };
+        self.camera.zoom = f32::min(
+            rect.width / self.rect.width * CAMERA_DEFAULT_ZOOM,
+            rect.height / self.rect.height * CAMERA_DEFAULT_ZOOM,
+        );
+    }
+
+    /// Draws the game into the rectangle, tinting the cells in `heat`
+    pub fn draw<T: RaylibDraw>(
+        &self,
+        g: &Game,
+        rect: Rectangle,
+        on_screen_rect: &Rectangle,
+        d: &mut T,
+        no_check: bool,
+        alpha: bool,
+        hover: Option<&[usize]>,
+        heat: &[(Move, Color)],
+    ) {
+        let mut c = d.begin_mode2D(self.camera);
+
+        // Draws the background
+        c.draw_rectangle_rec(
+            rect,
+            if g.board.check() != Value::None {
+                match g.board.check() {
+                    Value::None => panic!("How the fuck did you manage that"),
+                    Value::Draw => COLOUR_BOARD_BG_GREYED,
+                    Value::Player1 => self.player_1.get_greyed_colour(),
+                    Value::Player2 => self.player_2.get_greyed_colour(),
+                }
+            } else if g.legal.is_empty() {
+                COLOUR_BOARD_BG
+            } else if g.turn == Turn::Player1 {
+                self.player_1.get_greyed_colour()
+            } else {
+                self.player_2.get_greyed_colour()
+            },
+        );
+
+        // Draws the background for the board
+        let legal: Legal = if g.board.check() != Value::None || g.moves.is_empty() {
+            Legal::ForceDefaultBg
+        } else {
+            Legal::Pos(&g.legal)
+        };
+
+        // Draws the board
+        g.board.draw(
+            View::inner_rect(rect),
+            on_screen_rect,
+            &mut c,
+            no_check,
+            alpha,
+            hover,
+            &heat
+                .iter()
+                .map(|(pos, colour)| (&pos[..], *colour))
+                .collect::<Vec<_>>(),
+            legal,
+            g.turn,
+            &self.player_1,
+            &self.player_2,
+        );
+    }
+
+    /// Returns the cell of `g` under the given point in world space
+    pub fn get_cell_from_pixel(&self, g: &Game, point: Vector2, no_check: bool) -> Option<Move> {
+        g.board
+            .get_cell_from_pixel(View::inner_rect(self.rect), point, no_check)
+    }
+}
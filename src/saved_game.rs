@@ -0,0 +1,18 @@
+use game::game::Game;
+use serde::{Deserialize, Serialize};
+
+use crate::render::view::View;
+
+/// A game and the way it was being displayed, as written to and read from `.xo` files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// The game itself
+    #[serde(flatten)]
+    pub game: Game,
+    /// The rectangle and symbols the game was displayed with
+    #[serde(flatten)]
+    pub view: View,
+    /// The number of hints each player used, which older files don't have
+    #[serde(default)]
+    pub hints: [usize; 2],
+}
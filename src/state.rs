@@ -1,8 +1,11 @@
+use ai::{
+    engine::Engine, monte_carlo_stop::MonteCarloStop, noughbert_message::NoughbertMessage,
+    thorn_message::ThornMessage, thoughts::Thoughts,
+};
+use game::Move;
 use raylib::math::{Rectangle, Vector2};
 
-use crate::{
-    ai::{noughbert_message::NoughbertMessage, thoughts::Thoughts}, common::Move, fonts::Fonts, ui::textbox::Textbox
-};
+use crate::{fonts::Fonts, hint::Hint, render::view::View, ui::textbox::Textbox};
 
 /// Struct holding the main application state
 pub struct State {
@@ -16,6 +19,10 @@ pub struct State {
     pub waiting_for_thoughts: bool,
     /// Stores a queue of messages to be sent to the AI thread
     pub message_queue: Vec<NoughbertMessage>,
+    /// Stores a queue of messages to be sent to the Thorn thread
+    pub thorn_queue: Vec<ThornMessage>,
+    /// Stores a queue of messages to be sent to the Bramble thread
+    pub bramble_queue: Vec<NoughbertMessage>,
     /// Stores a list of the incoming moves from the AI thread
     pub move_queue: Vec<Move>,
     /// Stores a list of the incoming moves from the AI thread
@@ -38,4 +45,26 @@ pub struct State {
     pub ui_rect: Rectangle,
     /// Stores the loaded fonts
     pub fonts: Fonts,
+    /// Stores whether the AI should analyse the current position whenever it isn't moving
+    pub analysing: bool,
+    /// Stores the hash of the position being analysed, if an analysis is running
+    pub analysis_hash: Option<u64>,
+    /// Stores the engine running the analysis, or the last to run one
+    pub analysis_engine: Engine,
+    /// Stores the hash of the last position briefly analysed for the evaluation bar
+    pub evaluated_hash: Option<u64>,
+    /// Stores how much longer the brief analysis for the evaluation bar should run
+    pub evaluation_timer: f32,
+    /// Stores the last evaluation of the game from -1 to 1, relative to the first player
+    pub evaluation: f32,
+    /// Stores the last hint given, if any
+    pub hint: Option<Hint>,
+    /// Stores how much longer the AI should think about a requested hint
+    pub hint_timer: f32,
+    /// Stores the number of hints each player has used this game
+    pub hints_used: [usize; 2],
+    /// Stores why the computer stopped thinking about its last move, if known
+    pub stop_reason: Option<MonteCarloStop>,
+    /// Stores the camera and symbols used to draw the game
+    pub view: View,
 }
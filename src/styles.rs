@@ -2,7 +2,7 @@ use std::f32::consts::SQRT_2;
 
 use raylib::color::Color;
 
-use crate::game::{player::Player, symbol::Symbol};
+use crate::render::{player::Player, symbol::Symbol};
 
 //----------// Constants determining debug settings //----------//
 
@@ -12,25 +12,6 @@ pub const DEFAULT_SHOW_FPS_COUNTER: bool = false;
 /// Enables the FPS counter keybind
 pub const ALLOW_FPS_COUNTER: bool = true;
 
-/// An enum representing the possible graphvis debug levels
-#[derive(Debug, PartialEq, Eq)]
-pub enum GraphvisOutputLevel {
-    /// Prints after every recieved playout
-    Full,
-
-    /// Prints after every set of simulations
-    FinalsOnly,
-
-    /// Does not print
-    None,
-}
-/// Enables the logging of tree graphs
-// pub const OUTPUT_GRAPHVIS_FILES: bool = true;
-pub const OUTPUT_GRAPHVIS_FILES: GraphvisOutputLevel = GraphvisOutputLevel::FinalsOnly;
-
-/// Enables the automatic compilation of tree graphs to .svg files
-pub const AUTOCOMPILE_GRAPHVIS_FILES: bool = true;
-
 //----------// Constants determining UI settings //----------//
 
 /// Specifies the width of the UI panel
@@ -43,7 +24,7 @@ pub const UI_DIVIDER_THICKNESS: usize = 10;
 pub const UI_BUTTON_LINE_THICKNESS: usize = 8;
 
 /// Specifies the minimum height of the UI panel
-pub const UI_PANEL_MIN_HEIGHT: usize = 600;
+pub const UI_PANEL_MIN_HEIGHT: usize = 900;
 
 /// Specifies the height of the UI navbar (pixels)
 pub const UI_NAVBAR_HEIGHT: usize = 100;
@@ -54,6 +35,15 @@ pub const UI_CONTENT_PADDING: f32 = 0.05;
 /// Specifies how far the UI content scrolls per scroll tick
 pub const UI_SCROLL_SPEED: f32 = 35.0;
 
+/// Specifies the width of the evaluation bar beside the board (pixels)
+pub const UI_EVALUATION_BAR_WIDTH: f32 = 24.0;
+
+/// Specifies how many candidate moves are listed in analysis mode
+pub const UI_ANALYSIS_CANDIDATES: usize = 3;
+
+/// Specifies how many moves of each candidate's expected continuation are listed
+pub const UI_ANALYSIS_PV_LENGTH: usize = 5;
+
 //----------// Constants determining default game settings //----------//
 
 /// Specifies the default depth of a board
@@ -85,6 +75,9 @@ pub const COMPUTER_LEVEL_2_SIMS: usize = 30;
 /// The default sims for a level 3 AI
 pub const COMPUTER_LEVEL_3_SIMS: usize = 100;
 
+/// The AI strength that plays perfectly in solvable positions, searching like level 3 otherwise
+pub const COMPUTER_PERFECT_STRENGTH: usize = 4;
+
 /// The default scale factor for each depth
 pub const COMPUTER_SIM_SCALING: usize = 6;
 
@@ -99,6 +92,15 @@ pub const RULES_URL: &str = "https://thesnake66six.gitlab.io/snvc-rules";
 /// The default frequency for calling `Message::GetThoughts()`
 pub const DEFAULT_THOUGHTS_DELAY: f32 = 0.1;
 
+/// How long the AI thinks about a hint (seconds)
+pub const HINT_TIME: f32 = 1.5;
+
+/// How long each new position is analysed for in 2-player games, to update the evaluation bar (seconds)
+pub const EVALUATION_ANALYSIS_TIME: f32 = 1.0;
+
+/// Sets whether the AI thinks during the human's turn in 1-player games by default
+pub const DEFAULT_PONDER: bool = true;
+
 //----------// Constants determining the properties of the camera //----------//
 
 /// Governs how fast the camera moves when panning.
@@ -330,6 +332,17 @@ pub const COLOUR_DRAW_BGA: Color = Color {
 
 //-----// Miscellaneous //-----//
 
+/// The overlay imposed upon a cell suggested by a hint
+pub const COLOUR_HINT: Color = Color {
+    r: 255,
+    g: 203,
+    b: 0,
+    a: 200,
+};
+
+/// The opacity of the heat-map tint on the most visited (or surest) cell
+pub const COLOUR_HEAT_MAP_ALPHA: u8 = 200;
+
 /// The overlay imposed upon a hovered cell
 pub const COLOUR_CELL_HOVER: Color = Color {
     r: 190,
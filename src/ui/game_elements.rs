@@ -5,6 +5,10 @@ pub struct GameElements {
     pub padding_1: Rectangle,
     pub moves: Rectangle,
     pub padding_2: Rectangle,
+    pub analysis: Rectangle,
+    pub analysis_toggle: Rectangle,
+    pub hint: Rectangle,
+    pub padding_3: Rectangle,
     pub export: Rectangle,
 }
 
@@ -15,6 +19,10 @@ impl GameElements {
             padding_1: Rectangle::EMPTY,
             moves: Rectangle::EMPTY,
             padding_2: Rectangle::EMPTY,
+            analysis: Rectangle::EMPTY,
+            analysis_toggle: Rectangle::EMPTY,
+            hint: Rectangle::EMPTY,
+            padding_3: Rectangle::EMPTY,
             export: Rectangle::EMPTY,
         }
     }
@@ -13,12 +13,26 @@ pub struct SettingsElements {
     pub ai_1: Rectangle,
     pub ai_2: Rectangle,
     pub ai_3: Rectangle,
+    pub ai_perfect: Rectangle,
     pub ai_settings: Rectangle,
     pub ai_max_sims: Rectangle,
     pub ai_max_time: Rectangle,
+    pub engines: Rectangle,
+    pub engine_1: Rectangle,
+    pub engine_2: Rectangle,
+    pub policy_1: Rectangle,
+    pub policy_2: Rectangle,
     pub threads: Rectangle,
     pub threads_plus: Rectangle,
     pub threads_minus: Rectangle,
+    pub advanced: Rectangle,
+    pub exploration: Rectangle,
+    pub playout: Rectangle,
+    pub parallelism: Rectangle,
+    pub heat_map: Rectangle,
+    pub ponder: Rectangle,
+    pub book: Rectangle,
+    pub transpositions: Rectangle,
     pub rules: Rectangle,
     pub keybinds: Rectangle,
     pub symbols: Rectangle,
@@ -39,12 +53,26 @@ impl SettingsElements {
             ai_1: Rectangle::EMPTY,
             ai_2: Rectangle::EMPTY,
             ai_3: Rectangle::EMPTY,
+            ai_perfect: Rectangle::EMPTY,
             ai_settings: Rectangle::EMPTY,
             ai_max_sims: Rectangle::EMPTY,
             ai_max_time: Rectangle::EMPTY,
+            engines: Rectangle::EMPTY,
+            engine_1: Rectangle::EMPTY,
+            engine_2: Rectangle::EMPTY,
+            policy_1: Rectangle::EMPTY,
+            policy_2: Rectangle::EMPTY,
             threads: Rectangle::EMPTY,
             threads_plus: Rectangle::EMPTY,
             threads_minus: Rectangle::EMPTY,
+            advanced: Rectangle::EMPTY,
+            exploration: Rectangle::EMPTY,
+            playout: Rectangle::EMPTY,
+            parallelism: Rectangle::EMPTY,
+            heat_map: Rectangle::EMPTY,
+            ponder: Rectangle::EMPTY,
+            book: Rectangle::EMPTY,
+            transpositions: Rectangle::EMPTY,
             rules: Rectangle::EMPTY,
             keybinds: Rectangle::EMPTY,
             symbols: Rectangle::EMPTY,
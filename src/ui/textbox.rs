@@ -4,6 +4,8 @@ pub enum Textbox {
     MaxSims,
     /// The Max Time textbox
     MaxTime,
+    /// The exploration factor textbox
+    Exploration,
     /// No textbox selected
     None,
 }
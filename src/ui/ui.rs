@@ -5,15 +5,18 @@ use raylib::{
     text,
 };
 
+use ai::monte_carlo_proof::MonteCarloProof;
+use game::{
+    game::{Game, Turn},
+    value::Value,
+};
+
 use crate::{
-    common::centre_text_rec,
-    game::{
-        game::{Game, Turn},
-        value::Value,
-    },
+    common::{centre_text_rec, winner_moves},
     state::State,
     styles::{
         COLOUR_UI_BG, COLOUR_UI_BUTTON, COLOUR_UI_DIVIDER, COLOUR_UI_ELEMENT, COLOUR_UI_RADIAL,
+        COMPUTER_PERFECT_STRENGTH, UI_ANALYSIS_CANDIDATES, UI_ANALYSIS_PV_LENGTH,
         UI_BUTTON_LINE_THICKNESS, UI_CONTENT_PADDING, UI_DIVIDER_THICKNESS, UI_NAVBAR_HEIGHT,
         UI_PANEL_WIDTH,
     },
@@ -134,7 +137,10 @@ impl UI {
             x: r.x,
             y: r.y + r.height + padding,
             width: r.width,
-            height: inner_content.height - r.height * 2.0 - p.height * 2.0,
+            height: inner_content.height
+                - r.height * 2.0
+                - 100.0 * (UI_ANALYSIS_CANDIDATES + 1) as f32
+                - p.height * 3.0,
         };
         self.game_elements.moves = r;
 
@@ -146,6 +152,38 @@ impl UI {
         };
         self.game_elements.padding_2 = p;
 
+        // Calculate the position of the analysis panel, with its toggle as the top row
+        let r = Rectangle {
+            x: r.x,
+            y: p.y + p.height,
+            width: r.width,
+            height: 100.0 * (UI_ANALYSIS_CANDIDATES + 1) as f32,
+        };
+        self.game_elements.analysis = r;
+
+        // The top row is shared between the analysis toggle and the hint button
+        let half_width = (r.width - 3.0 * padding) / 2.0;
+        self.game_elements.analysis_toggle = Rectangle {
+            x: r.x + padding,
+            y: r.y + padding,
+            width: half_width,
+            height: 100.0 - 2.0 * padding,
+        };
+        self.game_elements.hint = Rectangle {
+            x: r.x + 2.0 * padding + half_width,
+            y: r.y + padding,
+            width: half_width,
+            height: 100.0 - 2.0 * padding,
+        };
+
+        let p = Rectangle {
+            x: p.x,
+            y: r.y + r.height,
+            width: p.width,
+            height: padding,
+        };
+        self.game_elements.padding_3 = p;
+
         let r = Rectangle {
             x: r.x,
             y: p.y + p.height,
@@ -188,7 +226,7 @@ impl UI {
             x: r.x,
             y: r.y + r.height + padding,
             width: r.width,
-            height: 200.0,
+            height: 300.0,
         };
         self.settings_elements.ai_strength = r;
 
@@ -196,10 +234,19 @@ impl UI {
             x: r.x,
             y: r.y + r.height + padding,
             width: r.width,
-            height: r.height,
+            height: 200.0,
         };
         self.settings_elements.ai_settings = r;
 
+        // Calculate the position of the engine and policy selectors
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + r.height + padding,
+            width: r.width,
+            height: 400.0,
+        };
+        self.settings_elements.engines = r;
+
         // Calculate the position of the threads buttons
 
         let r = Rectangle {
@@ -211,6 +258,15 @@ impl UI {
 
         self.settings_elements.threads = r;
 
+        // Calculate the position of the advanced AI settings
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + r.height + padding,
+            width: r.width,
+            height: 800.0,
+        };
+        self.settings_elements.advanced = r;
+
         // Calculate the position of the Rules button
         let r = Rectangle {
             x: r.x,
@@ -263,7 +319,7 @@ impl UI {
             x: r.x,
             y: r.y + r.height + padding,
             width: r.width,
-            height: 700.0,
+            height: 800.0,
         };
         self.keybinds_elements.binds = r;
 
@@ -371,6 +427,15 @@ impl UI {
         };
         self.settings_elements.ai_3 = r;
 
+        // The perfect play button sits under level 3, with its label to the left
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + button_side + padding,
+            width: button_side,
+            height: button_side,
+        };
+        self.settings_elements.ai_perfect = r;
+
         // Calculate positions of the AI text boxes
         let ai = self.settings_elements.ai_settings;
         let column_width = (ai.width - padding) / 2.0;
@@ -391,6 +456,42 @@ impl UI {
         };
         self.settings_elements.ai_max_time = r;
 
+        // Calculate the positions of the engine and policy buttons
+        let en = self.settings_elements.engines;
+        let column_width = (en.width - padding) / 2.0;
+        let p = column_width * UI_CONTENT_PADDING;
+        let r = Rectangle {
+            x: en.x + column_width + p,
+            y: en.y + (100.0 - button_side) / 2.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.engine_1 = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.policy_1 = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.engine_2 = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.policy_2 = r;
+
         // Calculate the positions of the threads buttons
         let th = self.settings_elements.threads;
 
@@ -410,6 +511,66 @@ impl UI {
         };
         self.settings_elements.threads_plus = r;
 
+        // Calculate the positions of the advanced AI settings' buttons, below the section title
+        let ad = self.settings_elements.advanced;
+        let column_width = (ad.width - padding) / 2.0;
+        let p = column_width * UI_CONTENT_PADDING;
+        let r = Rectangle {
+            x: ad.x + column_width + p,
+            y: ad.y + 100.0 + (100.0 - button_side) / 2.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.exploration = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.playout = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.parallelism = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.heat_map = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.ponder = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.book = r;
+
+        let r = Rectangle {
+            x: r.x,
+            y: r.y + 100.0,
+            width: column_width - p,
+            height: button_side,
+        };
+        self.settings_elements.transpositions = r;
+
         let p1 = self.symbols_elements.player_1;
         let b = Rectangle {
             x: p1.x + padding,
@@ -587,9 +748,9 @@ impl UI {
             d.draw_rectangle_rec(
                 rect,
                 if i % 2 == 1 {
-                    g.player_2.foreground
+                    state.view.player_2.foreground
                 } else {
-                    g.player_1.foreground
+                    state.view.player_1.foreground
                 },
             );
             let t = x[0]
@@ -619,8 +780,8 @@ impl UI {
 
         if g.board.check() != Value::None {
             let r = g.board.check();
-            let p1text = g.player_2.symbol.name() + " Win";
-            let p2text = g.player_2.symbol.name() + " Win";
+            let p1text = state.view.player_2.symbol.name() + " Win";
+            let p2text = state.view.player_2.symbol.name() + " Win";
             let text = match r {
                 Value::None => "Hardware error encountered",
                 Value::Draw => "Draw",
@@ -637,12 +798,12 @@ impl UI {
                 match r {
                     Value::None => Color::RED,
                     Value::Draw => Color::BLACK,
-                    Value::Player1 => g.player_1.foreground,
-                    Value::Player2 => g.player_2.foreground,
+                    Value::Player1 => state.view.player_1.foreground,
+                    Value::Player2 => state.view.player_2.foreground,
                 },
             );
         } else if g.turn == Turn::Player1 {
-            let text = &(g.player_1.symbol.name_apostrophe() + " Turn");
+            let text = &(state.view.player_1.symbol.name_apostrophe() + " Turn");
             let rec = centre_text_rec(&state.fonts.regular, text, 50.0, 0.0, tc);
 
             d.draw_text_ex(
@@ -651,10 +812,10 @@ impl UI {
                 Vector2 { x: rec.x, y: rec.y },
                 50.0,
                 0.0,
-                g.player_1.foreground,
+                state.view.player_1.foreground,
             );
         } else {
-            let text = &(g.player_2.symbol.name_apostrophe() + " Turn");
+            let text = &(state.view.player_2.symbol.name_apostrophe() + " Turn");
             let rec = centre_text_rec(&state.fonts.regular, text, 50.0, 0.0, tc);
             d.draw_text_ex(
                 &state.fonts.regular,
@@ -662,13 +823,142 @@ impl UI {
                 Vector2 { x: rec.x, y: rec.y },
                 50.0,
                 0.0,
-                g.player_2.foreground,
+                state.view.player_2.foreground,
             );
         }
 
         let p = self.game_elements.padding_2;
         d.draw_rectangle_rec(p, COLOUR_UI_BG);
 
+        // Draw the analysis panel
+        let an = self.game_elements.analysis;
+        d.draw_rectangle_rec(an, COLOUR_UI_ELEMENT);
+        // The buttons are bold while they are in use
+        let buttons = [
+            (
+                "Analyse",
+                state.analysing,
+                self.game_elements.analysis_toggle,
+            ),
+            ("Hint", state.hint_timer > 0.0, self.game_elements.hint),
+        ];
+        for (text, active, brec) in buttons {
+            d.draw_rectangle_rec(brec, COLOUR_UI_BUTTON);
+            let font = if active {
+                &state.fonts.bold
+            } else {
+                &state.fonts.regular
+            };
+            let trec = centre_text_rec(font, text, 50.0, 0.0, brec);
+            d.draw_text_ex(
+                font,
+                text,
+                Vector2 {
+                    x: trec.x,
+                    y: trec.y,
+                },
+                50.0,
+                0.0,
+                Color::BLACK,
+            );
+        }
+
+        let padding = UI_CONTENT_PADDING * rect.width;
+        let format_move = |x: &Vec<usize>| {
+            x.iter()
+                .map(|x| (x + 1).to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        // List the most simulated moves, relative to the player to move
+        if let (true, Some(t)) = (state.analysing, &state.currrent_thoughts) {
+            for (i, m) in t.moves.iter().take(UI_ANALYSIS_CANDIDATES).enumerate() {
+                let y = an.y + 100.0 * (i + 1) as f32;
+                let (mean, proven) = if g.turn == Turn::Player1 {
+                    (m.mean, m.proven)
+                } else {
+                    (-m.mean, m.proven.map(|p| p.flip()))
+                };
+                // The move itself is the first of the moves to the end, and is made by the player to move
+                let value = match proven {
+                    Some(MonteCarloProof::Win(n)) => {
+                        format!("Win in {}", winner_moves(n + 1, true))
+                    }
+                    Some(MonteCarloProof::Loss(n)) => {
+                        format!("Loss in {}", winner_moves(n + 1, false))
+                    }
+                    Some(MonteCarloProof::Draw) => "Draw".to_owned(),
+                    None => format!("{mean:+.2}"),
+                };
+                let text = format!("{}   {}   {}", format_move(&m.play), m.sims, value);
+                d.draw_text_ex(
+                    &state.fonts.bold,
+                    &text,
+                    Vector2 {
+                        x: an.x + padding,
+                        y: y + padding,
+                    },
+                    35.0,
+                    0.0,
+                    Color::BLACK,
+                );
+                let pv =
+                    m.pv.iter()
+                        .take(UI_ANALYSIS_PV_LENGTH)
+                        .map(format_move)
+                        .collect::<Vec<String>>()
+                        .join("  |  ");
+                d.draw_text_ex(
+                    &state.fonts.regular,
+                    &pv,
+                    Vector2 {
+                        x: an.x + padding,
+                        y: y + padding + 40.0,
+                    },
+                    25.0,
+                    0.0,
+                    Color::DARKGRAY,
+                );
+            }
+        } else if !state.analysing {
+            // Otherwise show the last hint, and how many each player has used
+            let lines = [
+                match &state.hint {
+                    Some(hint) => format!("Hint: {}", format_move(&hint.play)),
+                    None if state.hint_timer > 0.0 => "Thinking...".to_owned(),
+                    None => "No hint".to_owned(),
+                },
+                match &state.hint {
+                    Some(hint) => format!("Expected score: {:.2}", hint.expected_score),
+                    None => String::new(),
+                },
+                format!(
+                    "Hints: {} {}, {} {}",
+                    state.view.player_1.symbol.name(),
+                    state.hints_used[0],
+                    state.view.player_2.symbol.name(),
+                    state.hints_used[1]
+                ),
+            ];
+            for (i, text) in lines.iter().enumerate() {
+                d.draw_text_ex(
+                    &state.fonts.regular,
+                    text,
+                    Vector2 {
+                        x: an.x + padding,
+                        y: an.y + 100.0 * (i + 1) as f32 + padding,
+                    },
+                    40.0,
+                    0.0,
+                    Color::BLACK,
+                );
+            }
+        }
+
+        let p = self.game_elements.padding_3;
+        d.draw_rectangle_rec(p, COLOUR_UI_BG);
+
         let eb = self.game_elements.export;
         d.draw_rectangle_rec(eb, COLOUR_UI_ELEMENT);
         let text = "Export game";
@@ -1068,6 +1358,42 @@ impl UI {
             Color::BLACK,
         );
 
+        let mut ap = self.settings_elements.ai_perfect;
+        ap.y += self.scroll_offset_settings;
+        d.draw_rectangle_rec(ap, COLOUR_UI_BUTTON);
+        let p = button_side * UI_CONTENT_PADDING * 2.0;
+        if self.state.ai_strength == COMPUTER_PERFECT_STRENGTH && !self.state.is_ai_modified {
+            d.draw_rectangle_rec(
+                Rectangle {
+                    x: ap.x + p,
+                    y: ap.y + p,
+                    width: ap.width - 2.0 * p,
+                    height: ap.height - 2.0 * p,
+                },
+                COLOUR_UI_RADIAL,
+            );
+        }
+
+        let text = "Perfect play";
+        let trec = Rectangle {
+            x: ai.x + padding,
+            y: ap.y,
+            width: ap.x - ai.x - 2.0 * padding,
+            height: ap.height,
+        };
+        let ntrec = centre_text_rec(&state.fonts.regular, text, 50.0, 0.0, trec);
+        d.draw_text_ex(
+            &state.fonts.regular,
+            text,
+            Vector2 {
+                x: ntrec.x,
+                y: ntrec.y,
+            },
+            50.0,
+            0.0,
+            Color::BLACK,
+        );
+
         let mut ai = self.settings_elements.ai_settings;
         ai.y += self.scroll_offset_settings;
         d.draw_rectangle_rec(ai, COLOUR_UI_ELEMENT);
@@ -1169,6 +1495,60 @@ impl UI {
             Color::BLACK,
         );
 
+        // Draw the engine and policy selectors
+        let mut en = self.settings_elements.engines;
+        en.y += self.scroll_offset_settings;
+        d.draw_rectangle_rec(en, COLOUR_UI_ELEMENT);
+        let rows = [
+            (
+                self.state.player_1.name() + ":",
+                self.state.player_1_engine.name(),
+                self.settings_elements.engine_1,
+            ),
+            (
+                "Policy:".to_owned(),
+                self.state.player_1_policy.name(),
+                self.settings_elements.policy_1,
+            ),
+            (
+                self.state.player_2.name() + ":",
+                self.state.player_2_engine.name(),
+                self.settings_elements.engine_2,
+            ),
+            (
+                "Policy:".to_owned(),
+                self.state.player_2_policy.name(),
+                self.settings_elements.policy_2,
+            ),
+        ];
+        for (i, (text, button_text, mut brec)) in rows.into_iter().enumerate() {
+            d.draw_text_ex(
+                &state.fonts.regular,
+                &text,
+                Vector2 {
+                    x: en.x + padding,
+                    y: en.y + padding + 100.0 * i as f32,
+                },
+                50.0,
+                0.0,
+                Color::BLACK,
+            );
+            brec.y += self.scroll_offset_settings;
+            d.draw_rectangle_rec(brec, COLOUR_UI_BUTTON);
+            let trec = centre_text_rec(&state.fonts.regular, &button_text, 40.0, 0.0, brec);
+            d.draw_text_ex(
+                &state.fonts.regular,
+                &button_text,
+                Vector2 {
+                    x: trec.x,
+                    y: trec.y,
+                },
+                40.0,
+                0.0,
+                Color::BLACK,
+            );
+        }
+
         // Draw the threads selector
         let mut th = self.settings_elements.threads;
         th.y += self.scroll_offset_settings;
@@ -1250,6 +1630,110 @@ impl UI {
             Color::BLACK,
         );
 
+        // Draw the advanced AI settings
+        let mut ad = self.settings_elements.advanced;
+        ad.y += self.scroll_offset_settings;
+        d.draw_rectangle_rec(ad, COLOUR_UI_ELEMENT);
+        let text = "Advanced AI";
+        let trec = centre_text_rec(
+            &state.fonts.bold,
+            text,
+            50.0,
+            0.0,
+            Rectangle {
+                height: 100.0,
+                ..ad
+            },
+        );
+        d.draw_text_ex(
+            &state.fonts.bold,
+            text,
+            Vector2 {
+                x: trec.x,
+                y: trec.y,
+            },
+            50.0,
+            0.0,
+            Color::BLACK,
+        );
+
+        // The exploration factor is shown as typed while it is being edited
+        let exploration = if state.typing == Textbox::Exploration {
+            format!("{}_", self.state.exploration_factor)
+        } else {
+            format!("{:.2}", self.state.exploration())
+        };
+        let rows = [
+            (
+                "Exploration:",
+                exploration,
+                self.settings_elements.exploration,
+            ),
+            (
+                "Playouts:",
+                self.state.playout.name(),
+                self.settings_elements.playout,
+            ),
+            (
+                "Parallelism:",
+                self.state.parallelism.name(),
+                self.settings_elements.parallelism,
+            ),
+            (
+                "Overlay:",
+                self.state.heat_map.name(),
+                self.settings_elements.heat_map,
+            ),
+            (
+                "Ponder:",
+                if self.state.ponder { "On" } else { "Off" }.to_owned(),
+                self.settings_elements.ponder,
+            ),
+            ("Book:", self.state.book.name(), self.settings_elements.book),
+            (
+                "Transpose:",
+                if self.state.transpositions {
+                    "On"
+                } else {
+                    "Off"
+                }
+                .to_owned(),
+                self.settings_elements.transpositions,
+            ),
+        ];
+        for (i, (text, button_text, mut brec)) in rows.into_iter().enumerate() {
+            d.draw_text_ex(
+                &state.fonts.regular,
+                text,
+                Vector2 {
+                    x: ad.x + padding,
+                    y: ad.y + padding + 100.0 * (i + 1) as f32,
+                },
+                50.0,
+                0.0,
+                Color::BLACK,
+            );
+            brec.y += self.scroll_offset_settings;
+            d.draw_rectangle_rec(brec, COLOUR_UI_BUTTON);
+            let font = if i == 0 && state.typing == Textbox::Exploration {
+                &state.fonts.bold
+            } else {
+                &state.fonts.regular
+            };
+            let trec = centre_text_rec(font, &button_text, 40.0, 0.0, brec);
+            d.draw_text_ex(
+                font,
+                &button_text,
+                Vector2 {
+                    x: trec.x,
+                    y: trec.y,
+                },
+                40.0,
+                0.0,
+                Color::BLACK,
+            );
+        }
+
         let mut rs = self.settings_elements.rules;
         rs.y += self.scroll_offset_settings;
         d.draw_rectangle_rec(rs, COLOUR_UI_ELEMENT);
@@ -1588,6 +2072,45 @@ impl UI {
             Color::BLACK,
         );
 
+        let a = Rectangle {
+            x: a.x,
+            y: a.y + a.height,
+            width: a.width,
+            height: a.height,
+        };
+        let text = "H:";
+        let trec = centre_text_rec(&state.fonts.regular, text, 40.0, 0.0, a);
+        d.draw_text_ex(
+            &state.fonts.regular,
+            text,
+            Vector2 {
+                x: trec.x,
+                y: trec.y,
+            },
+            40.0,
+            0.0,
+            Color::BLACK,
+        );
+        let b = Rectangle {
+            x: a.x + a.width,
+            y: a.y,
+            width: a.width,
+            height: a.height,
+        };
+        let text = "Hint";
+        let trec = centre_text_rec(&state.fonts.regular, text, 40.0, 0.0, b);
+        d.draw_text_ex(
+            &state.fonts.regular,
+            text,
+            Vector2 {
+                x: trec.x,
+                y: trec.y,
+            },
+            40.0,
+            0.0,
+            Color::BLACK,
+        );
+
         let bk = self.keybinds_elements.back;
 
         d.draw_rectangle_rec(bk, COLOUR_UI_ELEMENT);
@@ -1,8 +1,15 @@
+use ai::{
+    book_mode::BookMode, engine::Engine, monte_carlo_parallelism::MonteCarloParallelism,
+    monte_carlo_policy::MonteCarloPolicy, playout_policy::PlayoutPolicy,
+};
+use game::game::Turn;
+
 use crate::{
-    game::symbol::Symbol,
+    render::{heat_map::HeatMapMode, symbol::Symbol},
     styles::{
         COMPUTER_DEFAULT_STRENGTH, COMPUTER_LEVEL_1_SIMS, COMPUTER_LEVEL_2_SIMS,
-        COMPUTER_LEVEL_3_SIMS, COMPUTER_SIM_SCALING, DEFAULT_MAX_THREADS,
+        COMPUTER_LEVEL_3_SIMS, COMPUTER_PERFECT_STRENGTH, COMPUTER_SIM_SCALING,
+        DEFAULT_EXPLORATION_FACTOR, DEFAULT_MAX_THREADS, DEFAULT_PONDER,
     },
     BOARD_DEFAULT_DEPTH, BOARD_DEFAULT_PLAYERS, DEFAULT_MAX_TIME,
 };
@@ -17,6 +24,20 @@ pub struct UIState {
     pub ai_threads: usize,
     pub player_1: Symbol,
     pub player_2: Symbol,
+    pub player_1_engine: Engine,
+    pub player_2_engine: Engine,
+    pub player_1_policy: MonteCarloPolicy,
+    pub player_2_policy: MonteCarloPolicy,
+    /// The exploration factor in hundredths, so that it can be typed like the other textboxes
+    pub exploration_factor: usize,
+    pub playout: PlayoutPolicy,
+    pub parallelism: MonteCarloParallelism,
+    pub heat_map: HeatMapMode,
+    /// Whether the AI keeps searching during the human's turn in 1-player games
+    pub ponder: bool,
+    pub book: BookMode,
+    /// Whether the AI shares statistics between move orders reaching the same position
+    pub transpositions: bool,
 }
 
 impl UIState {
@@ -29,7 +50,7 @@ impl UIState {
                 let l = match COMPUTER_DEFAULT_STRENGTH {
                     1 => COMPUTER_LEVEL_1_SIMS,
                     2 => COMPUTER_LEVEL_2_SIMS,
-                    3 => COMPUTER_LEVEL_3_SIMS,
+                    3 | COMPUTER_PERFECT_STRENGTH => COMPUTER_LEVEL_3_SIMS,
                     _ => 0,
                 };
                 l * (COMPUTER_SIM_SCALING.pow((BOARD_DEFAULT_DEPTH - 1).try_into().unwrap()))
@@ -39,6 +60,38 @@ impl UIState {
             ai_threads: DEFAULT_MAX_THREADS,
             player_1: Symbol::Cross,
             player_2: Symbol::Nought,
+            player_1_engine: Engine::Noughbert,
+            player_2_engine: Engine::Noughbert,
+            player_1_policy: MonteCarloPolicy::Robust,
+            player_2_policy: MonteCarloPolicy::Robust,
+            exploration_factor: (DEFAULT_EXPLORATION_FACTOR * 100.0).round() as usize,
+            playout: PlayoutPolicy::Random,
+            parallelism: MonteCarloParallelism::Tree,
+            heat_map: HeatMapMode::Off,
+            ponder: DEFAULT_PONDER,
+            book: BookMode::Best,
+            transpositions: false,
+        }
+    }
+
+    /// Returns the engine chosen to play for the given turn
+    pub fn engine(&self, turn: Turn) -> Engine {
+        match turn {
+            Turn::Player1 => self.player_1_engine,
+            Turn::Player2 => self.player_2_engine,
+        }
+    }
+
+    /// Returns the exploration factor as used by the search
+    pub fn exploration(&self) -> f32 {
+        self.exploration_factor as f32 / 100.0
+    }
+
+    /// Returns the move selection policy chosen for the given turn
+    pub fn policy(&self, turn: Turn) -> MonteCarloPolicy {
+        match turn {
+            Turn::Player1 => self.player_1_policy,
+            Turn::Player2 => self.player_2_policy,
         }
     }
 }